@@ -0,0 +1,55 @@
+use std::hint::black_box;
+
+use chimitheque_utils::formula::{to_empirical_formula, to_empirical_formulas};
+use criterion::{Criterion, criterion_group, criterion_main};
+
+/// A sample of formulas as found in product imports.
+const FORMULAS: [&str; 24] = [
+    "C6H5CH=CHCHO",
+    "CH3(CH2)5CH3",
+    "H2N(CH2)4CH(NH2)CO2H",
+    "CH3COO(CH2)3CH3",
+    "CH3N(NO)C(=NH)NHNO2",
+    "(CH3)2CHCH2COOH",
+    "C6H5CH2P(O)(OC2H5)2",
+    "[CH3CH2CH(C6H5)CO]2O",
+    "Li[CH(CH3)CH2CH3]3BH",
+    "[CH2=C(CH3)CH2PdCl]2",
+    "C15H10O7 · xH2O",
+    "NH2C(CH2OH)3",
+    "C62H87N13O16",
+    "C34H24N6Na4O14S4",
+    "C13H17HgNO6",
+    "Cr2K2O7",
+    "CuSO4·5H2O",
+    "Fe2(SO4)3",
+    "K4[Fe(CN)6]",
+    "CH3CH2OH",
+    "NaCl",
+    "H2O",
+    "CDCl3",
+    "C9H23NO3Si",
+];
+
+fn bench_to_empirical_formula(c: &mut Criterion) {
+    c.bench_function("to_empirical_formula", |b| {
+        b.iter(|| {
+            for formula in FORMULAS {
+                let _ = black_box(to_empirical_formula(black_box(formula)));
+            }
+        });
+    });
+}
+
+fn bench_to_empirical_formulas(c: &mut Criterion) {
+    c.bench_function("to_empirical_formulas", |b| {
+        b.iter(|| black_box(to_empirical_formulas(black_box(FORMULAS))));
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_to_empirical_formula,
+    bench_to_empirical_formulas
+);
+criterion_main!(benches);
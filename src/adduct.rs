@@ -0,0 +1,271 @@
+use log::debug;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+use crate::formula::monoisotopic_mass;
+
+/// Mass of the electron in u (CODATA 2018).
+pub const ELECTRON_MASS: f64 = 0.000_548_579_909;
+
+/// Common ESI and APCI adducts, positive then negative modes.
+pub const COMMON_ADDUCTS: [&str; 16] = [
+    "[M+H]+",
+    "[M+Na]+",
+    "[M+K]+",
+    "[M+NH4]+",
+    "[M+H-H2O]+",
+    "[M+2H]2+",
+    "[M+3H]3+",
+    "[2M+H]+",
+    "[2M+Na]+",
+    "[M-H]-",
+    "[M+Cl]-",
+    "[M+HCOO]-",
+    "[M+CH3COO]-",
+    "[M-H2O-H]-",
+    "[M-2H]2-",
+    "[2M-H]-",
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AdductError {
+    InvalidNotation(String),
+    InvalidMoleculeCount(String),
+    InvalidCharge(String),
+    EmptyTerm(String),
+}
+
+impl Display for AdductError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            AdductError::InvalidNotation(s) => write!(f, "invalid adduct notation {s}"),
+            AdductError::InvalidMoleculeCount(s) => write!(f, "invalid molecule count in {s}"),
+            AdductError::InvalidCharge(s) => write!(f, "invalid charge in {s}"),
+            AdductError::EmptyTerm(s) => write!(f, "empty term in {s}"),
+        }
+    }
+}
+
+impl std::error::Error for AdductError {}
+
+/// A formula added to or lost by the molecules, as `+Na` or `-H2O`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdductTerm {
+    /// The number of formulas, negative for a loss.
+    pub count: isize,
+    pub formula: String,
+}
+
+/// An ion formed from a molecule, as `[M+H]+` or `[2M+Na]+`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Adduct {
+    /// The notation as given to `parse_adduct`, trimmed.
+    pub notation: String,
+    /// The number of molecules, 2 in `[2M+H]+`.
+    pub molecule_count: usize,
+    pub terms: Vec<AdductTerm>,
+    /// The charge of the ion, -2 in `[M-2H]2-`.
+    pub charge: isize,
+}
+
+impl Adduct {
+    /// Returns the monoisotopic mass in u of the added and lost formulas, without the electrons.
+    pub fn mass_shift(&self) -> Result<f64, Box<dyn Error + Send + Sync>> {
+        let mut total = 0.0;
+
+        for term in &self.terms {
+            #[allow(clippy::cast_precision_loss)]
+            let count = term.count as f64;
+            total += count * monoisotopic_mass(&term.formula)?;
+        }
+
+        Ok(total)
+    }
+
+    /// Returns the m/z of the ion for a molecule of the given monoisotopic mass in u.
+    /// Each positive charge removes the mass of an electron, each negative charge adds it.
+    pub fn mz(&self, molecule_mass: f64) -> Result<f64, Box<dyn Error + Send + Sync>> {
+        #[allow(clippy::cast_precision_loss)]
+        let (molecule_count, charge, charge_count) = (
+            self.molecule_count as f64,
+            self.charge as f64,
+            self.charge.unsigned_abs() as f64,
+        );
+
+        let ion_mass = molecule_count * molecule_mass + self.mass_shift()? - charge * ELECTRON_MASS;
+
+        Ok(ion_mass / charge_count)
+    }
+}
+
+impl Display for Adduct {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.notation)
+    }
+}
+
+/// The m/z of an adduct of a formula.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdductMz {
+    pub adduct: Adduct,
+    pub mz: f64,
+}
+
+/// Parses an adduct notation, as `[M+H]+`, `[2M+Na]+`, `[M+2H]2+` or `[M-H2O+H]+`.
+/// Custom adducts are written the same way: `[M+CH3CN+H]+`.
+/// The charge follows the closing bracket, as `+`, `2+`, `+2` or `++`.
+pub fn parse_adduct(notation: &str) -> Result<Adduct, Box<dyn Error + Send + Sync>> {
+    let notation = notation.trim();
+    let invalid_notation = || Box::new(AdductError::InvalidNotation(notation.to_string()));
+
+    let Some((ion, charge)) = notation
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+    else {
+        return Err(invalid_notation());
+    };
+
+    let charge = parse_charge(charge)
+        .ok_or_else(|| Box::new(AdductError::InvalidCharge(notation.to_string())))?;
+
+    // The molecules: M or 2M.
+    let Some(molecule_index) = ion.find('M') else {
+        return Err(invalid_notation());
+    };
+    let molecule_count = match &ion[..molecule_index] {
+        "" => 1,
+        count => match count.parse::<usize>() {
+            Ok(count) if count > 0 => count,
+            _ => {
+                return Err(Box::new(AdductError::InvalidMoleculeCount(
+                    notation.to_string(),
+                )));
+            }
+        },
+    };
+
+    // The terms, each beginning with its sign.
+    let mut terms: Vec<AdductTerm> = Vec::new();
+    let mut rest = &ion[molecule_index + 1..];
+
+    while let Some(sign_char) = rest.chars().next() {
+        let sign = match sign_char {
+            '+' => 1,
+            '-' => -1,
+            _ => return Err(invalid_notation()),
+        };
+
+        let term_end = rest[1..]
+            .find(['+', '-'])
+            .map_or(rest.len(), |index| index + 1);
+        let term = rest[1..term_end].trim();
+        rest = &rest[term_end..];
+
+        let count_length = term.bytes().take_while(u8::is_ascii_digit).count();
+        let (count, formula) = term.split_at(count_length);
+        let formula = formula.trim();
+
+        if formula.is_empty() {
+            return Err(Box::new(AdductError::EmptyTerm(notation.to_string())));
+        }
+
+        let count = if count.is_empty() {
+            1
+        } else {
+            match count.parse::<isize>() {
+                Ok(count) if count > 0 => count,
+                _ => return Err(invalid_notation()),
+            }
+        };
+
+        // Validating the formula now rather than when computing the m/z.
+        monoisotopic_mass(formula)?;
+
+        terms.push(AdductTerm {
+            count: sign * count,
+            formula: formula.to_string(),
+        });
+    }
+
+    debug!("molecule_count: {molecule_count} terms: {terms:?} charge: {charge}");
+
+    Ok(Adduct {
+        notation: notation.to_string(),
+        molecule_count,
+        terms,
+        charge,
+    })
+}
+
+/// Parses the charge after the closing bracket: `+`, `2+`, `+2`, `++`.
+/// Returns `None` for a missing or a zero charge.
+fn parse_charge(charge: &str) -> Option<isize> {
+    let charge = charge.trim();
+
+    let digits = charge.trim_matches(['+', '-']);
+    let signs = charge
+        .strip_prefix(digits)
+        .or_else(|| charge.strip_suffix(digits))?;
+
+    let sign = match signs.chars().next()? {
+        '+' => 1,
+        _ => -1,
+    };
+
+    let value = if digits.is_empty() {
+        // Repeated signs: ++ or --.
+        if signs
+            .chars()
+            .any(|c| c != signs.chars().next().unwrap_or('+'))
+        {
+            return None;
+        }
+        isize::try_from(signs.len()).ok()?
+    } else {
+        if signs.len() != 1 {
+            return None;
+        }
+        digits.parse::<isize>().ok().filter(|value| *value > 0)?
+    };
+
+    Some(sign * value)
+}
+
+/// Returns the m/z of an adduct of a formula, from the monoisotopic masses.
+pub fn adduct_mz(formula: &str, adduct: &Adduct) -> Result<f64, Box<dyn Error + Send + Sync>> {
+    adduct.mz(monoisotopic_mass(formula)?)
+}
+
+/// Returns the m/z of the given adducts of a formula, in the given order.
+pub fn adduct_mzs(
+    formula: &str,
+    adducts: &[Adduct],
+) -> Result<Vec<AdductMz>, Box<dyn Error + Send + Sync>> {
+    let molecule_mass = monoisotopic_mass(formula)?;
+
+    adducts
+        .iter()
+        .map(|adduct| {
+            Ok(AdductMz {
+                adduct: adduct.clone(),
+                mz: adduct.mz(molecule_mass)?,
+            })
+        })
+        .collect()
+}
+
+/// Returns the m/z of the `COMMON_ADDUCTS` of a formula.
+pub fn common_adduct_mzs(formula: &str) -> Result<Vec<AdductMz>, Box<dyn Error + Send + Sync>> {
+    let adducts = COMMON_ADDUCTS
+        .iter()
+        .map(|notation| parse_adduct(notation))
+        .collect::<Result<Vec<Adduct>, _>>()?;
+
+    adduct_mzs(formula, &adducts)
+}
+
+#[cfg(test)]
+#[path = "adduct_tests.rs"]
+mod adduct_tests;
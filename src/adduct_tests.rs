@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::{adduct::*, test_helpers::assert_close};
+
+    fn init_logger() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    /// Tolerance of `assert_close`.
+    const TOLERANCE: f64 = 0.000_01;
+
+    const CAFFEINE: &str = "C8H10N4O2";
+
+    #[test]
+    fn test_parse_adduct() {
+        init_logger();
+
+        let adduct = parse_adduct("[M+H]+").unwrap();
+        assert_eq!(adduct.molecule_count, 1);
+        assert_eq!(
+            adduct.terms,
+            vec![AdductTerm {
+                count: 1,
+                formula: "H".to_string(),
+            }]
+        );
+        assert_eq!(adduct.charge, 1);
+
+        let adduct = parse_adduct(" [2M-2H+Na]- ").unwrap();
+        assert_eq!(adduct.notation, "[2M-2H+Na]-");
+        assert_eq!(adduct.molecule_count, 2);
+        assert_eq!(adduct.terms[0].count, -2);
+        assert_eq!(adduct.terms[1].formula, "Na");
+        assert_eq!(adduct.charge, -1);
+
+        assert_eq!(parse_adduct("[M+2H]2+").unwrap().charge, 2);
+        assert_eq!(parse_adduct("[M+2H]+2").unwrap().charge, 2);
+        assert_eq!(parse_adduct("[M+2H]++").unwrap().charge, 2);
+        assert_eq!(parse_adduct("[M-3H]3-").unwrap().charge, -3);
+        assert_eq!(parse_adduct("[M]+").unwrap().terms, vec![]);
+
+        for notation in COMMON_ADDUCTS {
+            assert!(parse_adduct(notation).is_ok(), "{notation}");
+        }
+
+        assert_eq!(
+            parse_adduct("M+H").unwrap_err().to_string(),
+            "invalid adduct notation M+H"
+        );
+        assert_eq!(
+            parse_adduct("[M+H]").unwrap_err().to_string(),
+            "invalid charge in [M+H]"
+        );
+        assert!(parse_adduct("[M+H]0+").is_err());
+        assert!(parse_adduct("[M+H]+-").is_err());
+        assert!(parse_adduct("[0M+H]+").is_err());
+        assert!(parse_adduct("[X+H]+").is_err());
+        assert!(parse_adduct("[M+]+").is_err());
+        assert!(parse_adduct("[M+Xy]+").is_err());
+    }
+
+    #[test]
+    fn test_adduct_mz() {
+        init_logger();
+
+        let mz = |notation: &str| adduct_mz(CAFFEINE, &parse_adduct(notation).unwrap()).unwrap();
+
+        assert_close(195.087_652, mz("[M+H]+"), TOLERANCE);
+        assert_close(217.069_596, mz("[M+Na]+"), TOLERANCE);
+        assert_close(212.114_201, mz("[M+NH4]+"), TOLERANCE);
+        assert_close(193.073_099, mz("[M-H]-"), TOLERANCE);
+        assert_close(229.049_777, mz("[M+Cl]-"), TOLERANCE);
+        assert_close(389.168_028, mz("[2M+H]+"), TOLERANCE);
+        assert_close(98.047_464, mz("[M+2H]2+"), TOLERANCE);
+        assert_close(96.032_911, mz("[M-2H]2-"), TOLERANCE);
+        // Custom adduct.
+        assert_close(236.114_201, mz("[M+CH3CN+H]+"), TOLERANCE);
+
+        assert!(adduct_mz("Xy", &parse_adduct("[M+H]+").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_common_adduct_mzs() {
+        init_logger();
+
+        let adduct_mzs = common_adduct_mzs(CAFFEINE).unwrap();
+        assert_eq!(adduct_mzs.len(), COMMON_ADDUCTS.len());
+        assert_eq!(adduct_mzs[0].adduct.to_string(), "[M+H]+");
+        assert_close(195.087_652, adduct_mzs[0].mz, TOLERANCE);
+
+        assert!(common_adduct_mzs("").is_err());
+    }
+}
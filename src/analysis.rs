@@ -0,0 +1,228 @@
+use log::debug;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+use crate::{
+    element::element_by_symbol,
+    formula::{ToEmpiricalFormulaError, to_atom_counts},
+};
+
+/// The usual acceptance for CHNS combustion analysis, in percent.
+pub const CHNS_TOLERANCE: f64 = 0.4;
+
+/// The elements measured by a CHNS combustion analysis.
+const CHNS_ATOMS: [&str; 4] = ["C", "H", "N", "S"];
+
+#[derive(Debug, PartialEq)]
+pub enum AnalysisError {
+    InvalidSolvateEquivalents(String, f64),
+    InvalidTolerance(f64),
+    InvalidMeasuredValue(String, f64),
+}
+
+impl Display for AnalysisError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            AnalysisError::InvalidSolvateEquivalents(s, equivalents) => {
+                write!(f, "invalid equivalents {equivalents} for solvate {s}")
+            }
+            AnalysisError::InvalidTolerance(tolerance) => {
+                write!(f, "invalid tolerance {tolerance}")
+            }
+            AnalysisError::InvalidMeasuredValue(s, value) => {
+                write!(f, "invalid measured value {value} for {s}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnalysisError {}
+
+/// A CHNS composition in percent by mass.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ChnsComposition {
+    pub carbon: f64,
+    pub hydrogen: f64,
+    pub nitrogen: f64,
+    pub sulfur: f64,
+}
+
+/// Measured CHNS values in percent by mass, `None` when the element was not measured.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MeasuredChns {
+    pub carbon: Option<f64>,
+    pub hydrogen: Option<f64>,
+    pub nitrogen: Option<f64>,
+    pub sulfur: Option<f64>,
+}
+
+/// The comparison of a measured value with the theoretical one for one element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementComparison {
+    pub atom: String,
+    pub theoretical: f64,
+    pub measured: f64,
+    /// Measured minus theoretical, in percent.
+    pub difference: f64,
+    pub pass: bool,
+}
+
+/// The comparison of measured CHNS values with the theoretical ones.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChnsComparison {
+    pub theoretical: ChnsComposition,
+    /// Only the measured elements, in the C, H, N, S order.
+    pub elements: Vec<ElementComparison>,
+    /// True if every measured element is within the tolerance.
+    pub pass: bool,
+}
+
+/// Returns the theoretical CHNS composition of a formula.
+/// Solvates are given with their equivalents per formula unit, as `("H2O", 0.5)` for an hemihydrate
+/// or `("CH2Cl2", 1.0)`.
+pub fn chns_composition(
+    formula: &str,
+    solvates: &[(&str, f64)],
+) -> Result<ChnsComposition, Box<dyn Error + Send + Sync>> {
+    // Atom counts may be fractional with the solvates.
+    let mut atom_counts: HashMap<String, f64> = HashMap::new();
+
+    add_atom_counts(&mut atom_counts, formula, 1.0)?;
+
+    for (solvate, equivalents) in solvates {
+        if !equivalents.is_finite() || *equivalents < 0.0 {
+            return Err(Box::new(AnalysisError::InvalidSolvateEquivalents(
+                (*solvate).to_string(),
+                *equivalents,
+            )));
+        }
+
+        add_atom_counts(&mut atom_counts, solvate, *equivalents)?;
+    }
+
+    debug!("atom_counts: {atom_counts:#?}");
+
+    // Computing the masses.
+    let mut total_mass = 0.0;
+    let mut atom_masses: HashMap<&str, f64> = HashMap::new();
+
+    for (atom_name, atom_count) in &atom_counts {
+        let Some(element) = element_by_symbol(atom_name) else {
+            return Err(Box::new(ToEmpiricalFormulaError::UnknowAtom(
+                atom_name.clone(),
+            )));
+        };
+
+        let atom_mass = element.atomic_weight * atom_count;
+        total_mass += atom_mass;
+
+        if CHNS_ATOMS.contains(&atom_name.as_str()) {
+            atom_masses.insert(atom_name, atom_mass);
+        }
+    }
+
+    if total_mass <= 0.0 {
+        return Err(Box::new(ToEmpiricalFormulaError::EmptyFormula));
+    }
+
+    let percent =
+        |atom: &str| atom_masses.get(atom).copied().unwrap_or_default() * 100.0 / total_mass;
+
+    let composition = ChnsComposition {
+        carbon: percent("C"),
+        hydrogen: percent("H"),
+        nitrogen: percent("N"),
+        sulfur: percent("S"),
+    };
+
+    debug!("composition: {composition:?}");
+
+    Ok(composition)
+}
+
+/// Compares measured CHNS values with the theoretical composition of a formula.
+/// An element passes if the absolute difference is at most `tolerance` percent, usually `CHNS_TOLERANCE`.
+/// See `chns_composition` for the solvates.
+pub fn compare_chns(
+    formula: &str,
+    solvates: &[(&str, f64)],
+    measured: &MeasuredChns,
+    tolerance: f64,
+) -> Result<ChnsComparison, Box<dyn Error + Send + Sync>> {
+    if !tolerance.is_finite() || tolerance < 0.0 {
+        return Err(Box::new(AnalysisError::InvalidTolerance(tolerance)));
+    }
+
+    let theoretical = chns_composition(formula, solvates)?;
+
+    let pairs = [
+        ("C", theoretical.carbon, measured.carbon),
+        ("H", theoretical.hydrogen, measured.hydrogen),
+        ("N", theoretical.nitrogen, measured.nitrogen),
+        ("S", theoretical.sulfur, measured.sulfur),
+    ];
+
+    let mut elements: Vec<ElementComparison> = Vec::new();
+
+    for (atom, theoretical_value, maybe_measured_value) in pairs {
+        let Some(measured_value) = maybe_measured_value else {
+            continue;
+        };
+
+        if !measured_value.is_finite() || !(0.0..=100.0).contains(&measured_value) {
+            return Err(Box::new(AnalysisError::InvalidMeasuredValue(
+                atom.to_string(),
+                measured_value,
+            )));
+        }
+
+        let difference = measured_value - theoretical_value;
+        // Rounding to avoid floating point noise on the tolerance bound.
+        let pass = (difference.abs() * 1e6).round() <= (tolerance * 1e6).round();
+
+        elements.push(ElementComparison {
+            atom: atom.to_string(),
+            theoretical: theoretical_value,
+            measured: measured_value,
+            difference,
+            pass,
+        });
+    }
+
+    let pass = elements.iter().all(|element| element.pass);
+
+    debug!("elements: {elements:#?} pass: {pass}");
+
+    Ok(ChnsComparison {
+        theoretical,
+        elements,
+        pass,
+    })
+}
+
+/// Adds the atoms of one formula unit of a formula, multiplied by `equivalents`.
+fn add_atom_counts(
+    atom_counts: &mut HashMap<String, f64>,
+    formula: &str,
+    equivalents: f64,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let formula_atom_counts = to_atom_counts(formula)?;
+    #[allow(clippy::cast_precision_loss)]
+    let formula_units = formula_atom_counts.formula_units() as f64;
+
+    for (element, atom_count) in formula_atom_counts.iter() {
+        #[allow(clippy::cast_precision_loss)]
+        let atom_count = atom_count as f64;
+        *atom_counts.entry(element.symbol.to_string()).or_default() +=
+            atom_count * equivalents / formula_units;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "analysis_tests.rs"]
+mod analysis_tests;
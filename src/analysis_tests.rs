@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::{analysis::*, test_helpers::assert_close};
+
+    fn init_logger() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    /// Tolerance of `assert_close`.
+    const TOLERANCE: f64 = 0.01;
+
+    #[test]
+    fn test_chns_composition() {
+        init_logger();
+
+        // Caffeine.
+        let composition = chns_composition("C8H10N4O2", &[]).unwrap();
+        assert_close(49.48, composition.carbon, TOLERANCE);
+        assert_close(5.19, composition.hydrogen, TOLERANCE);
+        assert_close(28.85, composition.nitrogen, TOLERANCE);
+        assert_close(0.0, composition.sulfur, TOLERANCE);
+
+        // Sulfanilamide.
+        let composition = chns_composition("H2NC6H4SO2NH2", &[]).unwrap();
+        assert_close(41.85, composition.carbon, TOLERANCE);
+        assert_close(4.68, composition.hydrogen, TOLERANCE);
+        assert_close(16.27, composition.nitrogen, TOLERANCE);
+        assert_close(18.62, composition.sulfur, TOLERANCE);
+
+        // Caffeine hemihydrate.
+        let composition = chns_composition("C8H10N4O2", &[("H2O", 0.5)]).unwrap();
+        assert_close(47.29, composition.carbon, TOLERANCE);
+        assert_close(5.46, composition.hydrogen, TOLERANCE);
+        assert_close(27.57, composition.nitrogen, TOLERANCE);
+
+        // Caffeine monohydrate, half of the water in the formula.
+        let composition = chns_composition("C8H10N4O2·0.5H2O", &[("H2O", 0.5)]).unwrap();
+        assert_close(45.28, composition.carbon, TOLERANCE);
+        assert_close(5.70, composition.hydrogen, TOLERANCE);
+        assert_close(26.40, composition.nitrogen, TOLERANCE);
+
+        assert!(chns_composition("", &[]).is_err());
+        assert!(chns_composition("()", &[]).is_err());
+        assert!(chns_composition("C8H10N4O2", &[("Xx", 1.0)]).is_err());
+
+        let error = chns_composition("C8H10N4O2", &[("H2O", -1.0)]).unwrap_err();
+        assert_eq!(error.to_string(), "invalid equivalents -1 for solvate H2O");
+    }
+
+    #[test]
+    fn test_compare_chns() {
+        init_logger();
+
+        let measured = MeasuredChns {
+            carbon: Some(49.21),
+            hydrogen: Some(5.30),
+            nitrogen: Some(28.20),
+            sulfur: None,
+        };
+
+        let comparison = compare_chns("C8H10N4O2", &[], &measured, CHNS_TOLERANCE).unwrap();
+        assert!(!comparison.pass);
+        assert_eq!(comparison.elements.len(), 3);
+        assert_eq!(comparison.elements[0].atom, "C");
+        assert!(comparison.elements[0].pass);
+        assert!(comparison.elements[1].pass);
+        assert_eq!(comparison.elements[2].atom, "N");
+        assert!(!comparison.elements[2].pass);
+        assert_close(-0.65, comparison.elements[2].difference, TOLERANCE);
+
+        // The same values match the hemihydrate.
+        let measured = MeasuredChns {
+            carbon: Some(47.10),
+            hydrogen: Some(5.30),
+            nitrogen: Some(27.40),
+            sulfur: None,
+        };
+        let comparison =
+            compare_chns("C8H10N4O2", &[("H2O", 0.5)], &measured, CHNS_TOLERANCE).unwrap();
+        assert!(comparison.pass);
+
+        // On the tolerance bound.
+        let measured = MeasuredChns {
+            carbon: Some(49.88),
+            ..MeasuredChns::default()
+        };
+        let comparison = compare_chns("C8H10N4O2", &[], &measured, 0.4).unwrap();
+        assert!(comparison.pass);
+
+        let error = compare_chns("C8H10N4O2", &[], &measured, -0.4).unwrap_err();
+        assert_eq!(error.to_string(), "invalid tolerance -0.4");
+
+        let measured = MeasuredChns {
+            carbon: Some(149.0),
+            ..MeasuredChns::default()
+        };
+        assert!(compare_chns("C8H10N4O2", &[], &measured, CHNS_TOLERANCE).is_err());
+    }
+}
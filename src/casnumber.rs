@@ -1,7 +1,14 @@
 use log::debug;
-use std::fmt::{Display, Formatter};
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
 
-use crate::define::{ALL_ZERO_RE, CAS_NUMBER_RE};
+use crate::{
+    checksum::weighted_digit_sum,
+    define::{ALL_ZERO_RE, CAS_NUMBER_RE},
+};
 
 #[derive(Debug, PartialEq)]
 pub enum CasNumberError {
@@ -11,6 +18,8 @@ pub enum CasNumberError {
     NoCheckDigitFound,
     CheckDigitDoesNotMatch,
     AllZeros,
+    UnexpectedCharacter(char),
+    AmbiguousCasNumber(String),
 }
 
 impl Display for CasNumberError {
@@ -24,6 +33,8 @@ impl Display for CasNumberError {
             CasNumberError::CheckDigitDoesNotMatch => write!(f, "check digit does not match"),
             CasNumberError::AllZeros => write!(f, "all zeros"),
             CasNumberError::EmptyCasNumber => write!(f, "empty CAS number"),
+            CasNumberError::UnexpectedCharacter(char) => write!(f, "unexpected character {char}"),
+            CasNumberError::AmbiguousCasNumber(s) => write!(f, "ambiguous CAS number {s}"),
         }
     }
 }
@@ -52,35 +63,7 @@ pub fn is_cas_number(number: &str) -> Result<(), Box<dyn std::error::Error + Sen
         return Err(Box::new(CasNumberError::AllZeros));
     }
 
-    // Multiplier that will increase at each operation.
-    let mut multiplier = 1;
-    // Total sum of each operation.
-    let mut total = 0;
-
-    let group2_reversed: String = group2.chars().rev().collect();
-
-    // Processing group2.
-    for digit_char in group2_reversed.chars() {
-        let Some(digit) = digit_char.to_digit(10) else {
-            return Err(Box::new(CasNumberError::CharTodigitConversion(digit_char)));
-        };
-        total += multiplier * digit;
-        multiplier += 1;
-    }
-
-    let group1_reversed: String = group1.chars().rev().collect();
-
-    // Processing group1.
-    for digit_char in group1_reversed.chars() {
-        let Some(digit) = digit_char.to_digit(10) else {
-            return Err(Box::new(CasNumberError::CharTodigitConversion(digit_char)));
-        };
-        total += multiplier * digit;
-        multiplier += 1;
-    }
-
-    // Calculating modulo.
-    let modulo = total % 10;
+    let modulo = compute_check_digit_from_groups(group1, group2)?;
     debug!("modulo:{modulo}");
 
     // Processing checkdigit.
@@ -91,7 +74,7 @@ pub fn is_cas_number(number: &str) -> Result<(), Box<dyn std::error::Error + Sen
 
         debug!("digit:{digit}");
 
-        if digit.eq(&modulo) {
+        if digit.eq(&u32::from(modulo)) {
             Ok(())
         } else {
             Err(Box::new(CasNumberError::CheckDigitDoesNotMatch))
@@ -101,6 +84,445 @@ pub fn is_cas_number(number: &str) -> Result<(), Box<dyn std::error::Error + Sen
     }
 }
 
+/// Computes the check digit from the first two groups of a CAS number:
+/// the sum of the digits weighted by their position from the right, modulo 10.
+fn compute_check_digit_from_groups(
+    group1: &str,
+    group2: &str,
+) -> Result<u8, Box<dyn std::error::Error + Send + Sync>> {
+    // Weights increase from the rightmost digit.
+    let modulo = weighted_digit_sum(group1.chars().chain(group2.chars()).rev(), 10)
+        .map_err(CasNumberError::CharTodigitConversion)?;
+
+    // The modulo is below 10.
+    Ok(u8::try_from(modulo)?)
+}
+
+/// Splits the first two groups of a CAS number from a string as `64-17`, `64-17-?` or `64-17-5`.
+/// The check digit, if any, is ignored.
+fn split_first_groups(
+    number: &str,
+) -> Result<(&str, &str), Box<dyn std::error::Error + Send + Sync>> {
+    if number.is_empty() {
+        return Err(Box::new(CasNumberError::EmptyCasNumber));
+    }
+
+    let mut parts = number.split('-');
+    let (Some(group1), Some(group2)) = (parts.next(), parts.next()) else {
+        return Err(Box::new(CasNumberError::DigitGroupsCapture));
+    };
+
+    let is_check_digit_valid = match (parts.next(), parts.next()) {
+        (None, None) => true,
+        (Some(check_digit), None) => {
+            check_digit == "?"
+                || (check_digit.len() == 1 && check_digit.bytes().all(|b| b.is_ascii_digit()))
+        }
+        _ => false,
+    };
+
+    if !is_check_digit_valid
+        || !(2..=7).contains(&group1.len())
+        || group2.len() != 2
+        || !group1
+            .bytes()
+            .chain(group2.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return Err(Box::new(CasNumberError::DigitGroupsCapture));
+    }
+
+    if ALL_ZERO_RE.is_match(group1) && ALL_ZERO_RE.is_match(group2) {
+        return Err(Box::new(CasNumberError::AllZeros));
+    }
+
+    Ok((group1, group2))
+}
+
+/// Computes the check digit of a CAS number from its first two groups,
+/// as `64-17`, `64-17-?` or `64-17-9` whose check digit is ignored: 5 for these three.
+pub fn compute_check_digit(number: &str) -> Result<u8, Box<dyn std::error::Error + Send + Sync>> {
+    let (group1, group2) = split_first_groups(number)?;
+
+    compute_check_digit_from_groups(group1, group2)
+}
+
+/// Completes a CAS number from its first two groups, as `64-17` or `64-17-?`,
+/// or corrects its check digit: `64-17-9` gives `64-17-5`.
+pub fn complete_cas_number(
+    number: &str,
+) -> Result<CasNumber, Box<dyn std::error::Error + Send + Sync>> {
+    let (group1, group2) = split_first_groups(number)?;
+
+    let check_digit = compute_check_digit_from_groups(group1, group2)?;
+    debug!("check_digit:{check_digit}");
+
+    Ok(CasNumber {
+        first_group: group1.parse()?,
+        second_group: group2.parse()?,
+        check_digit,
+    })
+}
+
+/// A CAS number that went through `is_cas_number`.
+/// Ordered as in the registry, by numeric value of the groups: `50-00-0` < `100-00-5`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CasNumber {
+    first_group: u32,
+    second_group: u8,
+    check_digit: u8,
+}
+
+impl CasNumber {
+    /// Returns the first group, 50 in `50-00-0`.
+    #[must_use]
+    pub fn first_group(&self) -> u32 {
+        self.first_group
+    }
+
+    /// Returns the second group, 0 in `50-00-0`.
+    #[must_use]
+    pub fn second_group(&self) -> u8 {
+        self.second_group
+    }
+
+    /// Returns the check digit, 5 in `100-00-5`.
+    #[must_use]
+    pub fn check_digit(&self) -> u8 {
+        self.check_digit
+    }
+}
+
+impl FromStr for CasNumber {
+    type Err = Box<dyn std::error::Error + Send + Sync>;
+
+    fn from_str(number: &str) -> Result<Self, Self::Err> {
+        is_cas_number(number)?;
+
+        let Some(captures) = CAS_NUMBER_RE.captures(number) else {
+            return Err(Box::new(CasNumberError::DigitGroupsCapture));
+        };
+
+        Ok(CasNumber {
+            first_group: captures["group1"].parse()?,
+            second_group: captures["group2"].parse()?,
+            check_digit: captures["checkdigit"].parse()?,
+        })
+    }
+}
+
+impl TryFrom<&str> for CasNumber {
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn try_from(number: &str) -> Result<Self, Self::Error> {
+        number.parse()
+    }
+}
+
+impl Display for CasNumber {
+    /// Writes the number without leading zeros, as `50-00-0`.
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{:02}-{:02}-{}",
+            self.first_group, self.second_group, self.check_digit
+        )
+    }
+}
+
+/// Prefixes removed by `normalize_cas_number`, the longest first, compared ignoring case.
+const CAS_NUMBER_PREFIXES: [&str; 6] = ["CAS RN", "CAS-RN", "CASRN", "CAS No.", "CAS No", "CAS"];
+
+/// Dashes converted into `-` by `normalize_cas_number` and `scan_identifiers`.
+pub(crate) const DASHES: [char; 6] = ['‐', '‑', '‒', '–', '—', '−'];
+
+/// A change made by `normalize_cas_number`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CasNumberChange {
+    RemovedPrefix(String),
+    RemovedWhitespace,
+    ConvertedDash(char),
+    InsertedDashes,
+    RemovedLeadingZeros(String),
+}
+
+impl Display for CasNumberChange {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            CasNumberChange::RemovedPrefix(s) => write!(f, "removed prefix {s:?}"),
+            CasNumberChange::RemovedWhitespace => write!(f, "removed whitespaces"),
+            CasNumberChange::ConvertedDash(c) => write!(f, "converted {c:?} into \"-\""),
+            CasNumberChange::InsertedDashes => write!(f, "inserted dashes"),
+            CasNumberChange::RemovedLeadingZeros(s) => write!(f, "removed leading zeros of {s}"),
+        }
+    }
+}
+
+/// A CAS number normalized by `normalize_cas_number` with the list of changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedCasNumber {
+    pub cas_number: CasNumber,
+    pub changes: Vec<CasNumberChange>,
+}
+
+/// Normalizes a CAS number from an import into its canonical form, then validates it.
+/// Accepted: `50000`, `50 00 0`, `0000050-00-0`, `CAS 50-00-0`, `CAS RN: 50-00-0`, unicode dashes
+/// and whitespaces (non-breaking spaces included). All give `50-00-0`.
+/// The input is rejected rather than guessed when the groups can not be told apart,
+/// as in `50 000`, and the check digit is never corrected.
+pub fn normalize_cas_number(
+    number: &str,
+) -> Result<NormalizedCasNumber, Box<dyn std::error::Error + Send + Sync>> {
+    let mut changes: Vec<CasNumberChange> = Vec::new();
+    let mut rest = number.trim();
+
+    if rest.is_empty() {
+        return Err(Box::new(CasNumberError::EmptyCasNumber));
+    }
+
+    // Removing the prefix and its separator.
+    for prefix in CAS_NUMBER_PREFIXES {
+        if rest.len() >= prefix.len()
+            && rest.is_char_boundary(prefix.len())
+            && rest[..prefix.len()].eq_ignore_ascii_case(prefix)
+        {
+            let after_prefix = rest[prefix.len()..]
+                .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, ':' | '#'));
+            // Only before a number: `CAS 50-00-0` and `CAS50-00-0`, the rest fails below.
+            if after_prefix.starts_with(|c: char| c.is_ascii_digit()) {
+                changes.push(CasNumberChange::RemovedPrefix(
+                    rest[..rest.len() - after_prefix.len()]
+                        .trim_end()
+                        .to_string(),
+                ));
+                rest = after_prefix;
+            }
+            break;
+        }
+    }
+
+    // Converting dashes, and splitting on dashes or whitespaces.
+    let mut converted = String::with_capacity(rest.len());
+    for current_char in rest.chars() {
+        match current_char {
+            '0'..='9' | '-' => converted.push(current_char),
+            c if DASHES.contains(&c) => {
+                if !changes.contains(&CasNumberChange::ConvertedDash(c)) {
+                    changes.push(CasNumberChange::ConvertedDash(c));
+                }
+                converted.push('-');
+            }
+            c if c.is_whitespace() => converted.push(' '),
+            c => return Err(Box::new(CasNumberError::UnexpectedCharacter(c))),
+        }
+    }
+
+    let parts: Vec<&str> = if converted.contains('-') {
+        converted.split('-').map(str::trim).collect()
+    } else {
+        converted.split_whitespace().collect()
+    };
+
+    // Whitespaces inside a group, as in `50 0-00-0`, can not be told apart from separators.
+    if parts.iter().any(|part| part.contains(' ')) {
+        return Err(Box::new(CasNumberError::AmbiguousCasNumber(
+            number.to_string(),
+        )));
+    }
+    if number.trim() != number || converted.contains(' ') {
+        changes.push(CasNumberChange::RemovedWhitespace);
+    }
+
+    let (group1, group2, check_digit) = match parts.as_slice() {
+        [group1, group2, check_digit] => (*group1, *group2, *check_digit),
+        [digits] if (5..=10).contains(&digits.len()) => {
+            changes.push(CasNumberChange::InsertedDashes);
+            let length = digits.len();
+            (
+                &digits[..length - 3],
+                &digits[length - 3..length - 1],
+                &digits[length - 1..],
+            )
+        }
+        _ => {
+            return Err(Box::new(CasNumberError::AmbiguousCasNumber(
+                number.to_string(),
+            )));
+        }
+    };
+
+    // Removing the leading zeros of the first group, keeping two digits.
+    let zero_count = group1
+        .bytes()
+        .take_while(|b| *b == b'0')
+        .count()
+        .min(group1.len().saturating_sub(2));
+    if zero_count > 0 {
+        changes.push(CasNumberChange::RemovedLeadingZeros(group1.to_string()));
+    }
+    let group1 = &group1[zero_count..];
+
+    let canonical_number = format!("{group1}-{group2}-{check_digit}");
+    debug!("canonical_number:{canonical_number} changes:{changes:?}");
+
+    Ok(NormalizedCasNumber {
+        cas_number: canonical_number.parse()?,
+        changes,
+    })
+}
+
+/// The typo corrected by a `CasNumberSuggestion`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CasNumberEdit {
+    /// Two adjacent digits swapped back.
+    Transposition,
+    /// One digit replaced.
+    Substitution,
+    /// One digit inserted.
+    MissingDigit,
+    /// One digit removed.
+    ExtraDigit,
+}
+
+impl CasNumberEdit {
+    /// The score of the edit, the most frequent typos first.
+    fn score(self) -> isize {
+        match self {
+            CasNumberEdit::Transposition => 3,
+            CasNumberEdit::Substitution => 2,
+            CasNumberEdit::MissingDigit | CasNumberEdit::ExtraDigit => 1,
+        }
+    }
+}
+
+/// A valid CAS number close to an invalid input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CasNumberSuggestion {
+    pub cas_number: CasNumber,
+    pub edit: CasNumberEdit,
+    /// The plausibility of the suggestion, the higher the better.
+    pub score: isize,
+}
+
+/// Returns the valid CAS numbers one typo away from an invalid one, ranked by plausibility:
+/// adjacent transpositions, then single-digit substitutions, then a missing or an extra digit.
+/// Dashes are ignored and set back from the digits count: `64-1-75` suggests `64-17-5`.
+/// `is_known`, if given, keeps only the numbers of a local dataset.
+/// Returns no suggestion for a valid number.
+pub fn suggest_cas_numbers(
+    number: &str,
+    is_known: Option<&dyn Fn(&CasNumber) -> bool>,
+) -> Result<Vec<CasNumberSuggestion>, Box<dyn std::error::Error + Send + Sync>> {
+    if number.is_empty() {
+        return Err(Box::new(CasNumberError::EmptyCasNumber));
+    }
+
+    if is_cas_number(number).is_ok() {
+        return Ok(Vec::new());
+    }
+
+    if let Some(c) = number.chars().find(|c| !c.is_ascii_digit() && *c != '-') {
+        return Err(Box::new(CasNumberError::UnexpectedCharacter(c)));
+    }
+
+    let digits: Vec<u8> = number
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|b| b - b'0')
+        .collect();
+
+    // Candidates in decreasing score order.
+    let mut candidates: Vec<(Vec<u8>, CasNumberEdit)> = Vec::new();
+
+    for index in 1..digits.len() {
+        if digits[index - 1] != digits[index] {
+            let mut candidate = digits.clone();
+            candidate.swap(index - 1, index);
+            candidates.push((candidate, CasNumberEdit::Transposition));
+        }
+    }
+
+    for index in 0..digits.len() {
+        for digit in (0..10).filter(|digit| *digit != digits[index]) {
+            let mut candidate = digits.clone();
+            candidate[index] = digit;
+            candidates.push((candidate, CasNumberEdit::Substitution));
+        }
+    }
+
+    for index in 0..=digits.len() {
+        for digit in 0..10 {
+            let mut candidate = digits.clone();
+            candidate.insert(index, digit);
+            candidates.push((candidate, CasNumberEdit::MissingDigit));
+        }
+    }
+
+    for index in 0..digits.len() {
+        let mut candidate = digits.clone();
+        candidate.remove(index);
+        candidates.push((candidate, CasNumberEdit::ExtraDigit));
+    }
+
+    let mut seen: HashSet<CasNumber> = HashSet::new();
+    let mut suggestions: Vec<CasNumberSuggestion> = Vec::new();
+
+    for (candidate, edit) in candidates {
+        let Some(cas_number) = cas_number_from_digits(&candidate) else {
+            continue;
+        };
+
+        if !seen.insert(cas_number) || is_known.is_some_and(|is_known| !is_known(&cas_number)) {
+            continue;
+        }
+
+        suggestions.push(CasNumberSuggestion {
+            cas_number,
+            edit,
+            score: edit.score(),
+        });
+    }
+
+    suggestions.sort_by(|a, b| b.score.cmp(&a.score).then(a.cas_number.cmp(&b.cas_number)));
+
+    debug!("suggestions: {suggestions:?}");
+
+    Ok(suggestions)
+}
+
+/// Builds a CAS number from its digits, if valid.
+fn cas_number_from_digits(digits: &[u8]) -> Option<CasNumber> {
+    let length = digits.len();
+    if !(5..=10).contains(&length) {
+        return None;
+    }
+
+    let to_number = |digits: &[u8]| {
+        digits
+            .iter()
+            .fold(0, |number, digit| number * 10 + u32::from(*digit))
+    };
+
+    let group1 = &digits[..length - 3];
+    let group2 = &digits[length - 3..length - 1];
+    let check_digit = digits[length - 1];
+
+    // Leading zeros are not canonical.
+    if group1[0] == 0 {
+        return None;
+    }
+
+    let cas_number = CasNumber {
+        first_group: to_number(group1),
+        second_group: u8::try_from(to_number(group2)).ok()?,
+        check_digit,
+    };
+
+    is_cas_number(&cas_number.to_string())
+        .is_ok()
+        .then_some(cas_number)
+}
+
 #[cfg(test)]
 #[path = "casnumber_tests.rs"]
 mod casnumber_tests;
@@ -280,6 +280,259 @@ mod tests {
         // All zeros
     }
 
+    #[test]
+    fn test_cas_number() {
+        init_logger();
+
+        let cas_number: CasNumber = "7732-18-5".parse().unwrap();
+        assert_eq!(cas_number.first_group(), 7732);
+        assert_eq!(cas_number.second_group(), 18);
+        assert_eq!(cas_number.check_digit(), 5);
+        assert_eq!(cas_number.to_string(), "7732-18-5");
+
+        assert_eq!(
+            CasNumber::try_from("0050-00-0").unwrap().to_string(),
+            "50-00-0"
+        );
+        assert_eq!(
+            CasNumber::try_from("0050-00-0").unwrap(),
+            CasNumber::try_from("50-00-0").unwrap()
+        );
+
+        assert_eq!(
+            "100-00-6".parse::<CasNumber>().unwrap_err().to_string(),
+            "check digit does not match"
+        );
+        assert!(CasNumber::try_from("").is_err());
+        assert!(CasNumber::try_from(" 50-00-0").is_err());
+
+        // Registry order, not string order.
+        let mut cas_numbers: Vec<CasNumber> = ["100-00-5", "50-00-0", "7732-18-5", "64-17-5"]
+            .iter()
+            .map(|number| number.parse().unwrap())
+            .collect();
+        cas_numbers.sort();
+        assert_eq!(
+            cas_numbers
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>(),
+            vec!["50-00-0", "64-17-5", "100-00-5", "7732-18-5"]
+        );
+
+        let cas_numbers: std::collections::HashSet<CasNumber> = ["64-17-5", "064-17-5"]
+            .iter()
+            .map(|number| number.parse().unwrap())
+            .collect();
+        assert_eq!(cas_numbers.len(), 1);
+    }
+
+    #[test]
+    fn test_compute_check_digit() {
+        init_logger();
+
+        assert_eq!(compute_check_digit("64-17").unwrap(), 5);
+        assert_eq!(compute_check_digit("64-17-?").unwrap(), 5);
+        assert_eq!(compute_check_digit("64-17-9").unwrap(), 5);
+        assert_eq!(compute_check_digit("7732-18").unwrap(), 5);
+        assert_eq!(compute_check_digit("50-00").unwrap(), 0);
+
+        assert_eq!(
+            compute_check_digit("").unwrap_err().to_string(),
+            "empty CAS number"
+        );
+        assert_eq!(
+            compute_check_digit("00-00").unwrap_err().to_string(),
+            "all zeros"
+        );
+        for number in [
+            "64",
+            "6-17",
+            "12345678-17",
+            "64-1",
+            "64-17-",
+            "64-17-55",
+            "64-17-5-1",
+            "64-1a",
+            "64 17",
+        ] {
+            assert_eq!(
+                compute_check_digit(number).unwrap_err().to_string(),
+                "can not capture digit groups",
+                "{number}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_complete_cas_number() {
+        init_logger();
+
+        assert_eq!(
+            complete_cas_number("64-17-?").unwrap().to_string(),
+            "64-17-5"
+        );
+        assert_eq!(complete_cas_number("64-17").unwrap().to_string(), "64-17-5");
+        assert_eq!(
+            complete_cas_number("64-17-9").unwrap().to_string(),
+            "64-17-5"
+        );
+        assert_eq!(
+            complete_cas_number("100683-97").unwrap().to_string(),
+            "100683-97-4"
+        );
+        assert!(complete_cas_number("64-").is_err());
+
+        // Every completed number is valid.
+        for number in ["10028-18", "12137-12", "101316-87", "50-00"] {
+            let cas_number = complete_cas_number(number).unwrap();
+            assert!(is_cas_number(&cas_number.to_string()).is_ok(), "{number}");
+        }
+    }
+
+    #[test]
+    fn test_normalize_cas_number() {
+        init_logger();
+
+        for number in [
+            "50-00-0",
+            "50000",
+            "50 00 0",
+            "0000050-00-0",
+            "CAS 50-00-0",
+            "CAS RN: 50-00-0",
+            "cas-rn 50-00-0",
+            "CAS No. 50-00-0",
+            "CAS#50-00-0",
+            "50–00–0",
+            "50\u{a0}00\u{a0}0",
+            " 50-00-0\n",
+            "50 - 00 - 0",
+        ] {
+            assert_eq!(
+                normalize_cas_number(number).unwrap().cas_number.to_string(),
+                "50-00-0",
+                "{number:?}"
+            );
+        }
+
+        assert_eq!(normalize_cas_number("50-00-0").unwrap().changes, vec![]);
+        assert_eq!(
+            normalize_cas_number("CAS RN: 0000050–00–0")
+                .unwrap()
+                .changes,
+            vec![
+                CasNumberChange::RemovedPrefix("CAS RN:".to_string()),
+                CasNumberChange::ConvertedDash('–'),
+                CasNumberChange::RemovedLeadingZeros("0000050".to_string()),
+            ]
+        );
+        assert_eq!(
+            normalize_cas_number("7732185").unwrap().changes,
+            vec![CasNumberChange::InsertedDashes]
+        );
+        assert_eq!(
+            normalize_cas_number("7732 18 5").unwrap().changes,
+            vec![CasNumberChange::RemovedWhitespace]
+        );
+        assert_eq!(
+            CasNumberChange::RemovedPrefix("CAS".to_string()).to_string(),
+            "removed prefix \"CAS\""
+        );
+
+        // Ambiguous inputs.
+        for number in [
+            "50 000",
+            "500 00",
+            "5 0-00-0",
+            "50-00",
+            "50-00-0-1",
+            "5000",
+            "12345678901",
+        ] {
+            assert!(normalize_cas_number(number).is_err(), "{number:?}");
+        }
+        assert_eq!(
+            normalize_cas_number("50 000").unwrap_err().to_string(),
+            "ambiguous CAS number 50 000"
+        );
+
+        // The check digit is never corrected.
+        assert_eq!(
+            normalize_cas_number("50001").unwrap_err().to_string(),
+            "check digit does not match"
+        );
+
+        assert!(normalize_cas_number("").is_err());
+        assert!(normalize_cas_number("CASE 50-00-0").is_err());
+        assert_eq!(
+            normalize_cas_number("50-00-0 (formaldehyde)")
+                .unwrap_err()
+                .to_string(),
+            "unexpected character ("
+        );
+    }
+
+    #[test]
+    fn test_suggest_cas_numbers() {
+        init_logger();
+
+        let to_strings = |suggestions: &[CasNumberSuggestion]| {
+            suggestions
+                .iter()
+                .map(|suggestion| suggestion.cas_number.to_string())
+                .collect::<Vec<String>>()
+        };
+
+        // Transposition.
+        let suggestions = suggest_cas_numbers("64-71-5", None).unwrap();
+        assert_eq!(suggestions[0].cas_number.to_string(), "64-17-5");
+        assert_eq!(suggestions[0].edit, CasNumberEdit::Transposition);
+        assert!(
+            suggestions
+                .iter()
+                .all(|suggestion| suggestion.score <= suggestions[0].score)
+        );
+
+        // Substitution.
+        let suggestions = suggest_cas_numbers("64-17-6", None).unwrap();
+        assert!(to_strings(&suggestions).contains(&"64-17-5".to_string()));
+        assert!(
+            suggestions
+                .iter()
+                .all(|suggestion| is_cas_number(&suggestion.cas_number.to_string()).is_ok())
+        );
+
+        // Missing and extra digits, dashes set back.
+        let suggestions = suggest_cas_numbers("7732-8-5", None).unwrap();
+        assert!(to_strings(&suggestions).contains(&"7732-18-5".to_string()));
+        let suggestions = suggest_cas_numbers("7732-188-5", None).unwrap();
+        assert!(to_strings(&suggestions).contains(&"7732-18-5".to_string()));
+        assert!(
+            suggestions
+                .iter()
+                .any(|suggestion| suggestion.edit == CasNumberEdit::ExtraDigit)
+        );
+
+        // Filtered by a local dataset.
+        let known = ["64-17-5", "7732-18-5"];
+        let is_known = |cas_number: &CasNumber| known.contains(&cas_number.to_string().as_str());
+        assert_eq!(
+            to_strings(&suggest_cas_numbers("64-17-6", Some(&is_known)).unwrap()),
+            vec!["64-17-5"]
+        );
+        assert_eq!(
+            suggest_cas_numbers("50-00-1", Some(&is_known)).unwrap(),
+            vec![]
+        );
+
+        // No suggestion for a valid number.
+        assert_eq!(suggest_cas_numbers("64-17-5", None).unwrap(), vec![]);
+
+        assert!(suggest_cas_numbers("", None).is_err());
+        assert!(suggest_cas_numbers("64-17-X", None).is_err());
+    }
+
     #[test]
     fn test_format_empty_cas_number_error() {
         let error = CasNumberError::EmptyCasNumber;
@@ -1,7 +1,13 @@
 use log::debug;
-use std::fmt::{Display, Formatter};
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
 
-use crate::define::{ALL_ZERO_RE, CE_NUMBER_RE};
+use crate::{
+    checksum::weighted_digit_sum,
+    define::{ALL_ZERO_RE, CE_NUMBER_RE},
+};
 
 #[derive(Debug, PartialEq)]
 pub enum CeNumberError {
@@ -11,6 +17,9 @@ pub enum CeNumberError {
     NoCheckDigitFound,
     CheckDigitDoesNotMatch,
     AllZeros,
+    CheckDigitIsTen,
+    RejectedListNumber,
+    UnexpectedCharacter(char),
 }
 
 impl Display for CeNumberError {
@@ -24,15 +33,125 @@ impl Display for CeNumberError {
             CeNumberError::CheckDigitDoesNotMatch => write!(f, "check digit does not match"),
             CeNumberError::AllZeros => write!(f, "all zeros"),
             CeNumberError::EmptyCeNumber => write!(f, "empty CE number"),
+            CeNumberError::CheckDigitIsTen => write!(
+                f,
+                "computed check digit is 10, such numbers are never assigned"
+            ),
+            CeNumberError::RejectedListNumber => {
+                write!(f, "ECHA list numbers are not EC numbers")
+            }
+            CeNumberError::UnexpectedCharacter(char) => write!(f, "unexpected character {char}"),
         }
     }
 }
 
 impl std::error::Error for CeNumberError {}
 
+/// The list an EC number comes from, given by its first digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EcNumberList {
+    /// European Inventory of Existing Commercial Chemical Substances, 2xx and 3xx.
+    Einecs,
+    /// European List of Notified Chemical Substances, 4xx.
+    Elincs,
+    /// No-Longer Polymers list, 5xx.
+    Nlp,
+    /// ECHA list numbers, 6xx, 7xx and 9xx: not official EC numbers.
+    EchaList,
+    /// Ranges not assigned: 0xx, 1xx and 8xx.
+    Unassigned,
+}
+
+impl EcNumberList {
+    fn from_first_group(first_group: u16) -> EcNumberList {
+        match first_group / 100 {
+            2 | 3 => EcNumberList::Einecs,
+            4 => EcNumberList::Elincs,
+            5 => EcNumberList::Nlp,
+            6 | 7 | 9 => EcNumberList::EchaList,
+            _ => EcNumberList::Unassigned,
+        }
+    }
+}
+
+/// How `parse_ec_number` handles the ECHA list numbers.
+/// Some list numbers have no valid check digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListNumberPolicy {
+    /// List numbers are checked as EC numbers.
+    #[default]
+    CheckDigit,
+    /// The check digit of list numbers is not checked.
+    IgnoreCheckDigit,
+    /// List numbers are rejected with `CeNumberError::RejectedListNumber`.
+    Reject,
+}
+
 /// <https://en.wikipedia.org/wiki/European_Community_number>
 /// Check if a string is a valid European Community number.
+/// ECHA list numbers are checked as EC numbers, see `is_ce_number_with_policy`.
+/// See `EcNumber` for a validated value.
 pub fn is_ce_number(number: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    number.parse::<EcNumber>().map(|_| ())
+}
+
+/// Check if a string is a valid European Community number, with a policy for ECHA list numbers.
+pub fn is_ce_number_with_policy(
+    number: &str,
+    policy: ListNumberPolicy,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    parse_ec_number(number, policy).map(|_| ())
+}
+
+/// Returns the list of a validated EC number.
+#[must_use]
+pub fn classify_ec_number(ec_number: &EcNumber) -> EcNumberList {
+    EcNumberList::from_first_group(ec_number.first_group)
+}
+
+/// A European Community number that went through `is_ce_number`, as `200-001-8`.
+/// Ordered by numeric value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EcNumber {
+    first_group: u16,
+    second_group: u16,
+    check_digit: u8,
+}
+
+impl EcNumber {
+    /// Returns the first group, 200 in `200-001-8`.
+    #[must_use]
+    pub fn first_group(&self) -> u16 {
+        self.first_group
+    }
+
+    /// Returns the second group, 1 in `200-001-8`.
+    #[must_use]
+    pub fn second_group(&self) -> u16 {
+        self.second_group
+    }
+
+    /// Returns the check digit, 8 in `200-001-8`.
+    #[must_use]
+    pub fn check_digit(&self) -> u8 {
+        self.check_digit
+    }
+}
+
+impl FromStr for EcNumber {
+    type Err = Box<dyn std::error::Error + Send + Sync>;
+
+    fn from_str(number: &str) -> Result<Self, Self::Err> {
+        parse_ec_number(number, ListNumberPolicy::default())
+    }
+}
+
+/// Parses a European Community number with a policy for the ECHA list numbers.
+/// A computed check digit of 10 gives `CeNumberError::CheckDigitIsTen`.
+pub fn parse_ec_number(
+    number: &str,
+    policy: ListNumberPolicy,
+) -> Result<EcNumber, Box<dyn std::error::Error + Send + Sync>> {
     // Rejecting empty numbers.
     if number.is_empty() {
         return Err(Box::new(CeNumberError::EmptyCeNumber));
@@ -52,35 +171,14 @@ pub fn is_ce_number(number: &str) -> Result<(), Box<dyn std::error::Error + Send
         return Err(Box::new(CeNumberError::AllZeros));
     }
 
-    // Multiplier that will increase at each operation.
-    let mut multiplier = 1;
-    // Total sum of each operation.
-    let mut total = 0;
+    let first_group: u16 = group1.parse()?;
+    let is_list_number = EcNumberList::from_first_group(first_group) == EcNumberList::EchaList;
 
-    // Processing group1.
-    for digit_char in group1.chars() {
-        let Some(digit) = digit_char.to_digit(10) else {
-            return Err(Box::new(CeNumberError::CharTodigitConversionerror(
-                digit_char,
-            )));
-        };
-        total += multiplier * digit;
-        multiplier += 1;
-    }
-
-    // Processing group2.
-    for digit_char in group2.chars() {
-        let Some(digit) = digit_char.to_digit(10) else {
-            return Err(Box::new(CeNumberError::CharTodigitConversionerror(
-                digit_char,
-            )));
-        };
-        total += multiplier * digit;
-        multiplier += 1;
+    if is_list_number && policy == ListNumberPolicy::Reject {
+        return Err(Box::new(CeNumberError::RejectedListNumber));
     }
 
-    // Calculating modulo.
-    let modulo = total % 11;
+    let modulo = compute_ec_check_value(group1, group2)?;
     debug!("modulo:{modulo}");
 
     // Processing checkdigit.
@@ -91,8 +189,16 @@ pub fn is_ce_number(number: &str) -> Result<(), Box<dyn std::error::Error + Send
             )));
         };
 
-        if digit.eq(&modulo) {
-            Ok(())
+        let is_check_digit_ignored = is_list_number && policy == ListNumberPolicy::IgnoreCheckDigit;
+
+        if digit.eq(&modulo) || is_check_digit_ignored {
+            Ok(EcNumber {
+                first_group,
+                second_group: group2.parse()?,
+                check_digit: u8::try_from(digit)?,
+            })
+        } else if modulo == 10 {
+            Err(Box::new(CeNumberError::CheckDigitIsTen))
         } else {
             Err(Box::new(CeNumberError::CheckDigitDoesNotMatch))
         }
@@ -101,6 +207,215 @@ pub fn is_ce_number(number: &str) -> Result<(), Box<dyn std::error::Error + Send
     }
 }
 
+impl TryFrom<&str> for EcNumber {
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn try_from(number: &str) -> Result<Self, Self::Error> {
+        number.parse()
+    }
+}
+
+impl Display for EcNumber {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{:03}-{:03}-{}",
+            self.first_group, self.second_group, self.check_digit
+        )
+    }
+}
+
+/// Computes the check value from the first two groups of an EC number:
+/// the sum of the digits weighted by their position from the left, modulo 11.
+/// The value is 10 for numbers that are never assigned.
+fn compute_ec_check_value(
+    group1: &str,
+    group2: &str,
+) -> Result<u32, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(weighted_digit_sum(group1.chars().chain(group2.chars()), 11)
+        .map_err(CeNumberError::CharTodigitConversionerror)?)
+}
+
+/// Splits the first two groups of an EC number from a string as `200-001`, `200-001-?`
+/// or `200-001-8`. The check digit, if any, is ignored.
+fn split_first_groups(
+    number: &str,
+) -> Result<(&str, &str), Box<dyn std::error::Error + Send + Sync>> {
+    if number.is_empty() {
+        return Err(Box::new(CeNumberError::EmptyCeNumber));
+    }
+
+    let mut parts = number.split('-');
+    let (Some(group1), Some(group2)) = (parts.next(), parts.next()) else {
+        return Err(Box::new(CeNumberError::DigitGroupsCaptureError));
+    };
+
+    let is_check_digit_valid = match (parts.next(), parts.next()) {
+        (None, None) => true,
+        (Some(check_digit), None) => {
+            check_digit == "?"
+                || (check_digit.len() == 1 && check_digit.bytes().all(|b| b.is_ascii_digit()))
+        }
+        _ => false,
+    };
+
+    if !is_check_digit_valid
+        || group1.len() != 3
+        || group2.len() != 3
+        || !group1
+            .bytes()
+            .chain(group2.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return Err(Box::new(CeNumberError::DigitGroupsCaptureError));
+    }
+
+    if ALL_ZERO_RE.is_match(group1) && ALL_ZERO_RE.is_match(group2) {
+        return Err(Box::new(CeNumberError::AllZeros));
+    }
+
+    Ok((group1, group2))
+}
+
+/// Computes the check digit of an EC number from its first two groups,
+/// as `200-001`, `200-001-?` or `200-001-3` whose check digit is ignored: 8 for these three.
+/// A computed value of 10 gives `CeNumberError::CheckDigitIsTen`.
+pub fn compute_ec_check_digit(
+    number: &str,
+) -> Result<u8, Box<dyn std::error::Error + Send + Sync>> {
+    let (group1, group2) = split_first_groups(number)?;
+
+    match compute_ec_check_value(group1, group2)? {
+        10 => Err(Box::new(CeNumberError::CheckDigitIsTen)),
+        value => Ok(u8::try_from(value)?),
+    }
+}
+
+/// Completes an EC number from its first two groups, as `200-001` or `200-001-?`,
+/// or corrects its check digit: `200-001-3` gives `200-001-8`.
+pub fn complete_ec_number(
+    number: &str,
+) -> Result<EcNumber, Box<dyn std::error::Error + Send + Sync>> {
+    let check_digit = compute_ec_check_digit(number)?;
+    let (group1, group2) = split_first_groups(number)?;
+
+    Ok(EcNumber {
+        first_group: group1.parse()?,
+        second_group: group2.parse()?,
+        check_digit,
+    })
+}
+
+/// Prefixes removed by `normalize_ec_number`, the longest first, compared ignoring case.
+const EC_NUMBER_PREFIXES: [&str; 6] = ["EINECS", "ELINCS", "EC No.", "EC No", "EC-No", "EC"];
+
+/// Dashes converted into `-` by `normalize_ec_number`.
+const DASHES: [char; 6] = ['‐', '‑', '‒', '–', '—', '−'];
+
+/// A change made by `normalize_ec_number`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EcNumberChange {
+    RemovedPrefix(String),
+    RemovedWhitespace,
+    ConvertedDash(char),
+    InsertedDashes,
+}
+
+impl Display for EcNumberChange {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            EcNumberChange::RemovedPrefix(s) => write!(f, "removed prefix {s:?}"),
+            EcNumberChange::RemovedWhitespace => write!(f, "removed whitespaces"),
+            EcNumberChange::ConvertedDash(c) => write!(f, "converted {c:?} into \"-\""),
+            EcNumberChange::InsertedDashes => write!(f, "inserted dashes"),
+        }
+    }
+}
+
+/// An EC number normalized by `normalize_ec_number` with the list of changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedEcNumber {
+    pub ec_number: EcNumber,
+    pub changes: Vec<EcNumberChange>,
+}
+
+/// Normalizes an EC number from an import into its canonical form, then validates it.
+/// Accepted: `2000018`, `200 001 8`, `EC 200-001-8`, `EINECS 200-001-8`, `EC No.: 200-001-8`,
+/// unicode dashes and whitespaces. All give `200-001-8`.
+/// The check digit is never corrected.
+pub fn normalize_ec_number(
+    number: &str,
+) -> Result<NormalizedEcNumber, Box<dyn std::error::Error + Send + Sync>> {
+    let mut changes: Vec<EcNumberChange> = Vec::new();
+    let mut rest = number.trim();
+
+    if rest.is_empty() {
+        return Err(Box::new(CeNumberError::EmptyCeNumber));
+    }
+
+    // Removing the prefix and its separator.
+    for prefix in EC_NUMBER_PREFIXES {
+        if rest.len() >= prefix.len()
+            && rest.is_char_boundary(prefix.len())
+            && rest[..prefix.len()].eq_ignore_ascii_case(prefix)
+        {
+            let after_prefix = rest[prefix.len()..]
+                .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, ':' | '#'));
+            // Only before a number: `EC 200-001-8` and `EC200-001-8`, the rest fails below.
+            if after_prefix.starts_with(|c: char| c.is_ascii_digit()) {
+                changes.push(EcNumberChange::RemovedPrefix(
+                    rest[..rest.len() - after_prefix.len()]
+                        .trim_end()
+                        .to_string(),
+                ));
+                rest = after_prefix;
+            }
+            break;
+        }
+    }
+
+    // Converting dashes and whitespaces.
+    let mut converted = String::with_capacity(rest.len());
+    for current_char in rest.chars() {
+        match current_char {
+            '0'..='9' | '-' => converted.push(current_char),
+            c if DASHES.contains(&c) => {
+                if !changes.contains(&EcNumberChange::ConvertedDash(c)) {
+                    changes.push(EcNumberChange::ConvertedDash(c));
+                }
+                converted.push('-');
+            }
+            c if c.is_whitespace() => converted.push(' '),
+            c => return Err(Box::new(CeNumberError::UnexpectedCharacter(c))),
+        }
+    }
+
+    let parts: Vec<&str> = if converted.contains('-') {
+        converted.split('-').map(str::trim).collect()
+    } else {
+        converted.split_whitespace().collect()
+    };
+
+    if number.trim() != number || converted.contains(' ') {
+        changes.push(EcNumberChange::RemovedWhitespace);
+    }
+
+    let canonical_number = match parts.as_slice() {
+        [group1, group2, check_digit] => format!("{group1}-{group2}-{check_digit}"),
+        [digits] if digits.len() == 7 => {
+            changes.push(EcNumberChange::InsertedDashes);
+            format!("{}-{}-{}", &digits[..3], &digits[3..6], &digits[6..])
+        }
+        _ => return Err(Box::new(CeNumberError::DigitGroupsCaptureError)),
+    };
+    debug!("canonical_number:{canonical_number} changes:{changes:?}");
+
+    Ok(NormalizedEcNumber {
+        ec_number: canonical_number.parse()?,
+        changes,
+    })
+}
+
 #[cfg(test)]
 #[path = "cenumber_tests.rs"]
 mod cenumber_tests;
@@ -207,6 +207,231 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ec_number() {
+        init_logger();
+
+        let ec_number: EcNumber = "200-001-8".parse().unwrap();
+        assert_eq!(ec_number.first_group(), 200);
+        assert_eq!(ec_number.second_group(), 1);
+        assert_eq!(ec_number.check_digit(), 8);
+        assert_eq!(ec_number.to_string(), "200-001-8");
+        assert_eq!(EcNumber::try_from("200-001-8").unwrap(), ec_number);
+
+        assert_eq!(
+            "200-001-3".parse::<EcNumber>().unwrap_err().to_string(),
+            "check digit does not match"
+        );
+        assert!(EcNumber::try_from("").is_err());
+
+        let mut ec_numbers: Vec<EcNumber> = ["231-791-2", "200-578-6", "200-001-8"]
+            .iter()
+            .map(|number| number.parse().unwrap())
+            .collect();
+        ec_numbers.sort();
+        assert_eq!(
+            ec_numbers
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>(),
+            vec!["200-001-8", "200-578-6", "231-791-2"]
+        );
+
+        let ec_numbers: std::collections::HashSet<EcNumber> = ["200-001-8", "200-001-8"]
+            .iter()
+            .map(|number| number.parse().unwrap())
+            .collect();
+        assert_eq!(ec_numbers.len(), 1);
+    }
+
+    #[test]
+    fn test_normalize_ec_number() {
+        init_logger();
+
+        for number in [
+            "200-001-8",
+            "2000018",
+            "200 001 8",
+            "EC 200-001-8",
+            "EINECS 200-001-8",
+            "ec no.: 200-001-8",
+            "EC200-001-8",
+            "200–001–8",
+            "200\u{a0}001\u{a0}8",
+            " 200-001-8 ",
+        ] {
+            assert_eq!(
+                normalize_ec_number(number).unwrap().ec_number.to_string(),
+                "200-001-8",
+                "{number:?}"
+            );
+        }
+
+        assert_eq!(normalize_ec_number("200-001-8").unwrap().changes, vec![]);
+        assert_eq!(
+            normalize_ec_number("EINECS 200–001–8").unwrap().changes,
+            vec![
+                EcNumberChange::RemovedPrefix("EINECS".to_string()),
+                EcNumberChange::ConvertedDash('–'),
+            ]
+        );
+        assert_eq!(
+            normalize_ec_number("2000018").unwrap().changes,
+            vec![EcNumberChange::InsertedDashes]
+        );
+        assert_eq!(
+            EcNumberChange::InsertedDashes.to_string(),
+            "inserted dashes"
+        );
+
+        // The check digit is never corrected.
+        assert_eq!(
+            normalize_ec_number("2000013").unwrap_err().to_string(),
+            "check digit does not match"
+        );
+        assert_eq!(
+            normalize_ec_number("200-001-8a").unwrap_err().to_string(),
+            "unexpected character a"
+        );
+        assert_eq!(
+            normalize_ec_number("ECHA 200-001-8")
+                .unwrap_err()
+                .to_string(),
+            "unexpected character E"
+        );
+        for number in [
+            "",
+            "200001",
+            "20000188",
+            "200 0018",
+            "2000-01-8",
+            "EC",
+            "ECHA 200-001-8",
+        ] {
+            assert!(normalize_ec_number(number).is_err(), "{number:?}");
+        }
+    }
+
+    #[test]
+    fn test_classify_ec_number() {
+        init_logger();
+
+        for (number, list) in [
+            ("200-001-8", EcNumberList::Einecs),
+            ("310-127-6", EcNumberList::Einecs),
+            ("500-001-0", EcNumberList::Nlp),
+            ("600-001-1", EcNumberList::EchaList),
+            ("700-001-2", EcNumberList::EchaList),
+            ("900-001-4", EcNumberList::EchaList),
+            ("100-001-7", EcNumberList::Unassigned),
+        ] {
+            let ec_number: EcNumber = number.parse().unwrap();
+            assert_eq!(classify_ec_number(&ec_number), list, "{number}");
+        }
+
+        let ec_number: EcNumber = "400-010-9".parse().unwrap();
+        assert_eq!(classify_ec_number(&ec_number), EcNumberList::Elincs);
+    }
+
+    #[test]
+    fn test_list_number_policy() {
+        init_logger();
+
+        // Checksum value 10.
+        assert_eq!(
+            is_ce_number("200-005-0").unwrap_err().to_string(),
+            "computed check digit is 10, such numbers are never assigned"
+        );
+        assert_eq!(
+            is_ce_number("200-001-9").unwrap_err().to_string(),
+            "check digit does not match"
+        );
+
+        // List numbers with an invalid check digit.
+        assert!(is_ce_number("600-001-5").is_err());
+        assert!(is_ce_number_with_policy("600-001-5", ListNumberPolicy::CheckDigit).is_err());
+        assert!(is_ce_number_with_policy("600-001-5", ListNumberPolicy::IgnoreCheckDigit).is_ok());
+        assert_eq!(
+            parse_ec_number("600-001-5", ListNumberPolicy::IgnoreCheckDigit)
+                .unwrap()
+                .to_string(),
+            "600-001-5"
+        );
+        assert!(is_ce_number_with_policy("200-001-9", ListNumberPolicy::IgnoreCheckDigit).is_err());
+
+        // List numbers rejected.
+        assert!(is_ce_number_with_policy("600-001-1", ListNumberPolicy::CheckDigit).is_ok());
+        assert_eq!(
+            is_ce_number_with_policy("600-001-1", ListNumberPolicy::Reject)
+                .unwrap_err()
+                .to_string(),
+            "ECHA list numbers are not EC numbers"
+        );
+        assert!(is_ce_number_with_policy("200-001-8", ListNumberPolicy::Reject).is_ok());
+    }
+
+    #[test]
+    fn test_compute_ec_check_digit() {
+        init_logger();
+
+        assert_eq!(compute_ec_check_digit("200-001").unwrap(), 8);
+        assert_eq!(compute_ec_check_digit("200-001-?").unwrap(), 8);
+        assert_eq!(compute_ec_check_digit("200-001-3").unwrap(), 8);
+        assert_eq!(compute_ec_check_digit("231-791").unwrap(), 2);
+
+        assert_eq!(
+            compute_ec_check_digit("200-005").unwrap_err().to_string(),
+            "computed check digit is 10, such numbers are never assigned"
+        );
+        assert_eq!(
+            compute_ec_check_digit("").unwrap_err().to_string(),
+            "empty CE number"
+        );
+        assert_eq!(
+            compute_ec_check_digit("000-000").unwrap_err().to_string(),
+            "all zeros"
+        );
+        for number in [
+            "200",
+            "20-001",
+            "200-01",
+            "200-001-",
+            "200-001-88",
+            "200-0a1",
+            "200 001",
+        ] {
+            assert_eq!(
+                compute_ec_check_digit(number).unwrap_err().to_string(),
+                "can not capture digit groups",
+                "{number}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_complete_ec_number() {
+        init_logger();
+
+        assert_eq!(
+            complete_ec_number("200-001-?").unwrap().to_string(),
+            "200-001-8"
+        );
+        assert_eq!(
+            complete_ec_number("200-001").unwrap().to_string(),
+            "200-001-8"
+        );
+        assert_eq!(
+            complete_ec_number("200-001-3").unwrap().to_string(),
+            "200-001-8"
+        );
+        assert!(complete_ec_number("200-005-?").is_err());
+
+        for number in ["214-480", "275-117", "600-001"] {
+            let ec_number = complete_ec_number(number).unwrap();
+            assert!(is_ce_number(&ec_number.to_string()).is_ok(), "{number}");
+        }
+    }
+
     #[test]
     fn test_format_empty_ce_number_error() {
         let error = CeNumberError::EmptyCeNumber;
@@ -242,4 +467,10 @@ mod tests {
         let error = CeNumberError::AllZeros;
         assert_eq!(error.to_string(), "all zeros");
     }
+
+    #[test]
+    fn test_format_unexpected_character_error() {
+        let error = CeNumberError::UnexpectedCharacter('a');
+        assert_eq!(error.to_string(), "unexpected character a");
+    }
 }
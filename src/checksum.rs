@@ -0,0 +1,18 @@
+/// Returns the sum of the digits weighted by their position, from 1, modulo `modulus`.
+/// Shared by the CAS and EC number check digits.
+/// Returns the first char that is not a digit as error.
+pub(crate) fn weighted_digit_sum(
+    digit_chars: impl Iterator<Item = char>,
+    modulus: u32,
+) -> Result<u32, char> {
+    let mut total = 0;
+
+    for (weight, digit_char) in (1..).zip(digit_chars) {
+        let Some(digit) = digit_char.to_digit(10) else {
+            return Err(digit_char);
+        };
+        total += weight * digit;
+    }
+
+    Ok(total % modulus)
+}
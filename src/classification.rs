@@ -0,0 +1,197 @@
+use log::debug;
+use std::{collections::HashMap, error::Error};
+
+use crate::{
+    element::{Element, element_by_symbol},
+    formula::to_atom_counts,
+};
+
+/// Carbon containing ions of inorganic compounds, as in `NaHCO3` or `HCN`.
+/// The longest first.
+const INORGANIC_CARBON_IONS: [&str; 5] = ["HCO3", "OCN", "SCN", "CO3", "CN"];
+
+/// The classification of a formula from its elements.
+/// Element lists are sorted and without duplicates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormulaClassification {
+    pub halogens: Vec<String>,
+    pub heavy_metals: Vec<String>,
+    pub radioactive_elements: Vec<String>,
+    pub metals: Vec<String>,
+    pub is_organic: bool,
+}
+
+impl FormulaClassification {
+    #[must_use]
+    pub fn contains_halogen(&self) -> bool {
+        !self.halogens.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains_heavy_metal(&self) -> bool {
+        !self.heavy_metals.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains_radioactive_element(&self) -> bool {
+        !self.radioactive_elements.is_empty()
+    }
+
+    /// An organic formula with a metal.
+    /// The formula does not tell the bonds: salts of organic acids, as `CH3COONa`, are reported too.
+    #[must_use]
+    pub fn is_organometallic(&self) -> bool {
+        self.is_organic && !self.metals.is_empty()
+    }
+
+    #[must_use]
+    pub fn is_inorganic(&self) -> bool {
+        !self.is_organic
+    }
+}
+
+/// Non-metal elements forming carbides with carbon, as `SiC` or `B4C`.
+const CARBIDE_ELEMENTS: [&str; 2] = ["B", "Si"];
+
+/// Classifies a formula from the categories of its elements.
+/// A formula is organic if it contains carbon outside of the inorganic carbon species:
+/// carbonates, cyanides, cyanates and thiocyanates (`NaHCO3`, `HCN`), carbon oxides (`CO`, `CO2`)
+/// carbides (`CaC2`, `SiC`) and elemental carbon (`C`, `C60`). `CCl4`, `C2Cl4` and perfluorocarbons
+/// are organic.
+pub fn classify_formula(
+    formula: &str,
+) -> Result<FormulaClassification, Box<dyn Error + Send + Sync>> {
+    let atom_counts = to_atom_counts(formula)?.to_hash_map();
+
+    let mut classification = classify_elements(
+        atom_counts
+            .keys()
+            .filter_map(|symbol| element_by_symbol(symbol)),
+    );
+
+    if atom_counts.contains_key("C") {
+        let organic_part = remove_inorganic_carbon_ions(formula);
+        let organic_atom_counts = to_atom_counts(&organic_part)
+            .unwrap_or_default()
+            .to_hash_map();
+
+        classification.is_organic = is_organic_carbon(&organic_atom_counts);
+    }
+
+    debug!("classification: {classification:?}");
+
+    Ok(classification)
+}
+
+/// Classifies a set of elements, as the components of an alloy.
+/// `is_organic` is left to false.
+pub fn classify_elements(
+    elements: impl IntoIterator<Item = &'static Element>,
+) -> FormulaClassification {
+    let mut elements: Vec<&Element> = elements.into_iter().collect();
+    elements.sort_by_key(|element| element.symbol);
+    elements.dedup_by_key(|element| element.symbol);
+
+    let mut classification = FormulaClassification::default();
+
+    for element in elements {
+        let symbol = element.symbol.to_string();
+
+        if element.is_halogen() {
+            classification.halogens.push(symbol.clone());
+        }
+        if element.is_heavy_metal() {
+            classification.heavy_metals.push(symbol.clone());
+        }
+        if element.is_radioactive() {
+            classification.radioactive_elements.push(symbol.clone());
+        }
+        if element.is_metal() {
+            classification.metals.push(symbol);
+        }
+    }
+
+    classification
+}
+
+/// Returns true if the formula contains a halogen.
+pub fn contains_halogen(formula: &str) -> Result<bool, Box<dyn Error + Send + Sync>> {
+    Ok(classify_formula(formula)?.contains_halogen())
+}
+
+/// Returns true if the formula contains an element of `HEAVY_METALS`.
+pub fn contains_heavy_metal(formula: &str) -> Result<bool, Box<dyn Error + Send + Sync>> {
+    Ok(classify_formula(formula)?.contains_heavy_metal())
+}
+
+/// Returns true if the formula contains an element without stable isotope.
+pub fn contains_radioactive_element(formula: &str) -> Result<bool, Box<dyn Error + Send + Sync>> {
+    Ok(classify_formula(formula)?.contains_radioactive_element())
+}
+
+/// Returns true if the formula is organic and contains a metal.
+/// See `FormulaClassification::is_organometallic`.
+pub fn is_organometallic(formula: &str) -> Result<bool, Box<dyn Error + Send + Sync>> {
+    Ok(classify_formula(formula)?.is_organometallic())
+}
+
+/// Returns true if the formula is not organic.
+/// See `classify_formula`.
+pub fn is_inorganic(formula: &str) -> Result<bool, Box<dyn Error + Send + Sync>> {
+    Ok(classify_formula(formula)?.is_inorganic())
+}
+
+/// Returns true if the atom counts, without the inorganic carbon ions, contain organic carbon.
+/// Without hydrogen, carbon alone or with only oxygen, metals or `CARBIDE_ELEMENTS` is inorganic.
+fn is_organic_carbon(atom_counts: &HashMap<String, usize>) -> bool {
+    if !atom_counts.contains_key("C") {
+        return false;
+    }
+
+    // Deuterium counts as hydrogen.
+    if atom_counts.contains_key("H") || atom_counts.contains_key("D") {
+        return true;
+    }
+
+    // Elemental carbon, as C60, has no other element and is inorganic.
+    !atom_counts
+        .keys()
+        .filter(|symbol| *symbol != "C")
+        .filter_map(|symbol| element_by_symbol(symbol))
+        .all(|element| {
+            element.symbol == "O"
+                || element.is_metal()
+                || CARBIDE_ELEMENTS.contains(&element.symbol)
+        })
+}
+
+/// Removes the inorganic carbon ions from a formula.
+/// An ion is removed only when it is not followed by a lowercase letter or a digit,
+/// so that `CN` is not read in `CNa` or `CO3` in `CO35`.
+fn remove_inorganic_carbon_ions(formula: &str) -> String {
+    let mut result = String::with_capacity(formula.len());
+    let mut rest = formula;
+
+    'outer: while let Some(current_char) = rest.chars().next() {
+        for ion in INORGANIC_CARBON_IONS {
+            if let Some(after_ion) = rest.strip_prefix(ion)
+                && !after_ion
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+            {
+                rest = after_ion;
+                continue 'outer;
+            }
+        }
+
+        result.push(current_char);
+        rest = &rest[current_char.len_utf8()..];
+    }
+
+    result
+}
+
+#[cfg(test)]
+#[path = "classification_tests.rs"]
+mod classification_tests;
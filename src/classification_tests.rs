@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::classification::*;
+
+    fn init_logger() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    #[test]
+    fn test_classify_formula() {
+        init_logger();
+
+        let classification = classify_formula("CH2Cl2").unwrap();
+        assert_eq!(classification.halogens, vec!["Cl".to_string()]);
+        assert!(classification.is_organic);
+        assert!(!classification.is_organometallic());
+
+        let classification = classify_formula("Pb(CH3COO)2").unwrap();
+        assert_eq!(classification.heavy_metals, vec!["Pb".to_string()]);
+        assert_eq!(classification.metals, vec!["Pb".to_string()]);
+        assert!(classification.is_organometallic());
+
+        let classification = classify_formula("HgCdTe").unwrap();
+        assert_eq!(
+            classification.heavy_metals,
+            vec!["Cd".to_string(), "Hg".to_string()]
+        );
+        assert!(classification.is_inorganic());
+
+        let classification = classify_formula("UO2(NO3)2·6H2O").unwrap();
+        assert_eq!(classification.radioactive_elements, vec!["U".to_string()]);
+        assert!(classification.is_inorganic());
+
+        // Halogenated organics.
+        let classification = classify_formula("C2Cl4").unwrap();
+        assert_eq!(classification.halogens, vec!["Cl".to_string()]);
+        assert!(classification.is_organic);
+        assert!(classify_formula("CCl4").unwrap().is_organic);
+
+        assert!(classify_formula("").is_err());
+        assert!(classify_formula("Xy").is_err());
+    }
+
+    #[test]
+    fn test_formula_predicates() {
+        init_logger();
+
+        assert!(contains_halogen("C6H5Br").unwrap());
+        assert!(contains_halogen("NaI").unwrap());
+        assert!(!contains_halogen("C6H6").unwrap());
+
+        assert!(contains_heavy_metal("K2Cr2O7").unwrap());
+        assert!(contains_heavy_metal("As2O3").unwrap());
+        assert!(!contains_heavy_metal("FeCl3").unwrap());
+
+        assert!(contains_radioactive_element("ThO2").unwrap());
+        assert!(contains_radioactive_element("RaCl2").unwrap());
+        assert!(!contains_radioactive_element("Bi2O3").unwrap());
+
+        assert!(is_organometallic("(C2H5)2Zn").unwrap());
+        assert!(is_organometallic("C4H9Li").unwrap());
+        assert!(!is_organometallic("C2H5OH").unwrap());
+        assert!(!is_organometallic("NaHCO3").unwrap());
+
+        assert!(is_inorganic("NaCl").unwrap());
+        assert!(is_inorganic("NaHCO3").unwrap());
+        assert!(is_inorganic("NH4HCO3").unwrap());
+        assert!(is_inorganic("HCN").unwrap());
+        assert!(is_inorganic("KSCN").unwrap());
+        assert!(is_inorganic("CaCO3").unwrap());
+        assert!(is_inorganic("CO2").unwrap());
+        assert!(is_inorganic("CO").unwrap());
+        assert!(is_inorganic("K4[Fe(CN)6]").unwrap());
+        assert!(is_inorganic("Ni(CO)4").unwrap());
+        assert!(is_inorganic("CaC2").unwrap());
+        assert!(is_inorganic("SiC").unwrap());
+
+        // Elemental carbon.
+        assert!(is_inorganic("C").unwrap());
+        assert!(is_inorganic("C60").unwrap());
+        assert!(is_inorganic("C70").unwrap());
+        assert!(!classify_formula("C60").unwrap().is_organic);
+
+        // Carbon compounds without hydrogen.
+        assert!(!is_inorganic("CCl4").unwrap());
+        assert!(!is_inorganic("C2Cl4").unwrap());
+        assert!(!is_inorganic("C6Cl6").unwrap());
+        assert!(!is_inorganic("C2F6").unwrap());
+        assert!(!is_inorganic("CH3CN").unwrap());
+        assert!(!is_inorganic("CH4").unwrap());
+        assert!(!is_inorganic("CDCl3").unwrap());
+        assert!(!is_inorganic("CH3COONa").unwrap());
+    }
+}
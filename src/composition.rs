@@ -0,0 +1,322 @@
+use log::debug;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+use crate::{
+    classification::{FormulaClassification, classify_elements},
+    element::{Element, element_by_symbol},
+};
+
+/// Tolerance in percent on the total of a composition.
+const TOTAL_TOLERANCE: f64 = 0.5;
+
+/// Basis markers accepted at the end of a composition, as in `Ni80Cr20 (wt%)`.
+const WEIGHT_PERCENT_MARKERS: [&str; 4] = ["wt%", "wt.%", "wt %", "w/w"];
+const ATOM_PERCENT_MARKERS: [&str; 4] = ["at%", "at.%", "at %", "mol%"];
+
+/// Nominal weight percent compositions of common alloys, by lowercase name.
+/// A component without percent is the balance.
+const NAMED_ALLOYS: [(&str, &str); 12] = [
+    ("stainless steel 304", "FeCr18Ni8"),
+    ("stainless steel 304l", "FeCr18Ni8"),
+    ("stainless steel 316", "FeCr17Ni12Mo2.5"),
+    ("stainless steel 316l", "FeCr17Ni12Mo2.5"),
+    ("brass", "Cu65Zn35"),
+    ("bronze", "Cu88Sn12"),
+    ("nichrome", "Ni80Cr20"),
+    ("constantan", "Cu55Ni45"),
+    ("sterling silver", "Ag92.5Cu7.5"),
+    ("solder", "Sn60Pb40"),
+    ("duralumin", "AlCu4.4Mg1.5Mn0.6"),
+    ("wood's metal", "Bi50Pb26.7Sn13.3Cd10"),
+];
+
+#[derive(Debug, PartialEq)]
+pub enum CompositionError {
+    EmptyComposition,
+    UnknownElement(String),
+    DuplicateElement(String),
+    InvalidPercent(String),
+    SeveralBalances,
+    InvalidTotal(f64),
+}
+
+impl Display for CompositionError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            CompositionError::EmptyComposition => write!(f, "empty composition"),
+            CompositionError::UnknownElement(s) => write!(f, "unknown element {s}"),
+            CompositionError::DuplicateElement(s) => write!(f, "duplicate element {s}"),
+            CompositionError::InvalidPercent(s) => write!(f, "invalid percent for {s}"),
+            CompositionError::SeveralBalances => write!(f, "more than one balance element"),
+            CompositionError::InvalidTotal(total) => write!(f, "total is {total}% instead of 100%"),
+        }
+    }
+}
+
+impl std::error::Error for CompositionError {}
+
+/// The basis of the percents of a composition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompositionBasis {
+    /// Weight percent, the default for alloys.
+    #[default]
+    WeightPercent,
+    /// Atom (mole) percent.
+    AtomPercent,
+}
+
+impl Display for CompositionBasis {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            CompositionBasis::WeightPercent => write!(f, "wt%"),
+            CompositionBasis::AtomPercent => write!(f, "at%"),
+        }
+    }
+}
+
+/// An element of a composition with its percent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompositionComponent {
+    pub element: &'static Element,
+    pub percent: f64,
+}
+
+/// The elemental composition of an alloy or a mixture, as `Cu60Zn40`.
+/// Components are in the order of the input, percents sum to 100.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Composition {
+    pub basis: CompositionBasis,
+    pub components: Vec<CompositionComponent>,
+}
+
+impl Composition {
+    /// Returns the percent of an element, 0 if absent.
+    #[must_use]
+    pub fn percent(&self, symbol: &str) -> f64 {
+        self.components
+            .iter()
+            .find(|component| component.element.symbol == symbol)
+            .map_or(0.0, |component| component.percent)
+    }
+
+    /// Returns the elements of the composition.
+    pub fn elements(&self) -> impl Iterator<Item = &'static Element> + '_ {
+        self.components.iter().map(|component| component.element)
+    }
+
+    /// Returns the composition in weight percent.
+    #[must_use]
+    pub fn to_weight_percent(&self) -> Composition {
+        match self.basis {
+            CompositionBasis::WeightPercent => self.clone(),
+            CompositionBasis::AtomPercent => self.convert(CompositionBasis::WeightPercent, |c| {
+                c.percent * c.element.atomic_weight
+            }),
+        }
+    }
+
+    /// Returns the composition in atom percent.
+    #[must_use]
+    pub fn to_atom_percent(&self) -> Composition {
+        match self.basis {
+            CompositionBasis::AtomPercent => self.clone(),
+            CompositionBasis::WeightPercent => self.convert(CompositionBasis::AtomPercent, |c| {
+                c.percent / c.element.atomic_weight
+            }),
+        }
+    }
+
+    /// Returns the classification of the elements, as for a formula.
+    #[must_use]
+    pub fn classification(&self) -> FormulaClassification {
+        classify_elements(self.elements())
+    }
+
+    /// Converts the percents with the given weight function and normalizes them to 100.
+    fn convert(
+        &self,
+        basis: CompositionBasis,
+        weight: impl Fn(&CompositionComponent) -> f64,
+    ) -> Composition {
+        let weights: Vec<f64> = self.components.iter().map(weight).collect();
+        let total: f64 = weights.iter().sum();
+
+        let components = self
+            .components
+            .iter()
+            .zip(weights)
+            .map(|(component, weight)| CompositionComponent {
+                element: component.element,
+                percent: weight * 100.0 / total,
+            })
+            .collect();
+
+        Composition { basis, components }
+    }
+}
+
+impl Display for Composition {
+    /// Writes the composition as `Cu60Zn40 (wt%)`, percents rounded to 0.01.
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        for component in &self.components {
+            let percent = (component.percent * 100.0).round() / 100.0;
+            write!(f, "{}{percent}", component.element.symbol)?;
+        }
+
+        write!(f, " ({})", self.basis)
+    }
+}
+
+/// Parses a composition, as `Cu60Zn40`, `Ni80Cr20 (wt%)`, `Ti50Ni50 at%` or a common alloy name
+/// as `stainless steel 316`.
+/// Percents are in weight percent unless followed by `at%`. One element without percent is the
+/// balance: `FeCr18Ni8` is `Fe74Cr18Ni8`. Whitespaces, `-`, `,` and `/` between the components
+/// are ignored. The total must be 100%.
+pub fn parse_composition(composition: &str) -> Result<Composition, Box<dyn Error + Send + Sync>> {
+    let composition = composition.trim();
+
+    let (composition, basis) = split_basis(composition);
+
+    let name = composition
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let composition = NAMED_ALLOYS
+        .iter()
+        .find(|(alloy_name, _)| alloy_name.eq_ignore_ascii_case(&name))
+        .map_or(composition, |(_, alloy_composition)| alloy_composition);
+
+    debug!("composition: {composition} basis: {basis}");
+
+    // Components as (element, percent), None for the balance.
+    let mut components: Vec<(&'static Element, Option<f64>)> = Vec::new();
+    let mut rest = composition;
+
+    while let Some(current_char) = rest.chars().next() {
+        if current_char.is_whitespace() || matches!(current_char, '-' | ',' | '/') {
+            rest = &rest[current_char.len_utf8()..];
+            continue;
+        }
+
+        let symbol_length = match rest.as_bytes() {
+            [b'A'..=b'Z', b'a'..=b'z', ..] => 2,
+            [b'A'..=b'Z', ..] => 1,
+            _ => {
+                let unknown_length = rest
+                    .find(|c: char| c.is_ascii_uppercase())
+                    .unwrap_or(rest.len());
+                return Err(Box::new(CompositionError::UnknownElement(
+                    rest[..unknown_length].to_string(),
+                )));
+            }
+        };
+        let symbol = &rest[..symbol_length];
+        rest = rest[symbol_length..].trim_start();
+
+        let Some(element) = element_by_symbol(symbol) else {
+            return Err(Box::new(CompositionError::UnknownElement(
+                symbol.to_string(),
+            )));
+        };
+
+        if components.iter().any(|(e, _)| e.symbol == element.symbol) {
+            return Err(Box::new(CompositionError::DuplicateElement(
+                symbol.to_string(),
+            )));
+        }
+
+        let percent_length = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let percent = &rest[..percent_length];
+        rest = &rest[percent_length..];
+
+        let percent = if percent.is_empty() {
+            None
+        } else {
+            match percent.parse::<f64>() {
+                Ok(percent) if percent > 0.0 && percent <= 100.0 => Some(percent),
+                _ => {
+                    return Err(Box::new(CompositionError::InvalidPercent(
+                        symbol.to_string(),
+                    )));
+                }
+            }
+        };
+
+        components.push((element, percent));
+    }
+
+    if components.is_empty() {
+        return Err(Box::new(CompositionError::EmptyComposition));
+    }
+
+    let total: f64 = components.iter().filter_map(|(_, percent)| *percent).sum();
+    let balance_count = components
+        .iter()
+        .filter(|(_, percent)| percent.is_none())
+        .count();
+
+    let balance = match balance_count {
+        0 => {
+            if (total - 100.0).abs() > TOTAL_TOLERANCE {
+                return Err(Box::new(CompositionError::InvalidTotal(total)));
+            }
+            0.0
+        }
+        1 => {
+            if total >= 100.0 {
+                return Err(Box::new(CompositionError::InvalidTotal(total)));
+            }
+            100.0 - total
+        }
+        _ => return Err(Box::new(CompositionError::SeveralBalances)),
+    };
+
+    let components = components
+        .into_iter()
+        .map(|(element, percent)| CompositionComponent {
+            element,
+            percent: percent.unwrap_or(balance),
+        })
+        .collect();
+
+    Ok(Composition { basis, components })
+}
+
+/// Splits the basis marker from the end of a composition, in parenthesis or not.
+fn split_basis(composition: &str) -> (&str, CompositionBasis) {
+    for (markers, basis) in [
+        (WEIGHT_PERCENT_MARKERS, CompositionBasis::WeightPercent),
+        (ATOM_PERCENT_MARKERS, CompositionBasis::AtomPercent),
+    ] {
+        for marker in markers {
+            let without_parenthesis = composition
+                .strip_suffix(')')
+                .map(str::trim_end)
+                .and_then(|rest| strip_suffix_ignore_case(rest, marker))
+                .and_then(|rest| rest.trim_end().strip_suffix('('));
+
+            if let Some(rest) =
+                without_parenthesis.or_else(|| strip_suffix_ignore_case(composition, marker))
+            {
+                return (rest.trim_end(), basis);
+            }
+        }
+    }
+
+    (composition, CompositionBasis::default())
+}
+
+fn strip_suffix_ignore_case<'a>(s: &'a str, suffix: &str) -> Option<&'a str> {
+    let index = s.len().checked_sub(suffix.len())?;
+
+    (s.is_char_boundary(index) && s[index..].eq_ignore_ascii_case(suffix)).then(|| &s[..index])
+}
+
+#[cfg(test)]
+#[path = "composition_tests.rs"]
+mod composition_tests;
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::{composition::*, test_helpers::assert_close};
+
+    fn init_logger() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    /// Tolerance of `assert_close`.
+    const TOLERANCE: f64 = 0.01;
+
+    #[test]
+    fn test_parse_composition() {
+        init_logger();
+
+        let composition = parse_composition("Cu60Zn40").unwrap();
+        assert_eq!(composition.basis, CompositionBasis::WeightPercent);
+        assert_eq!(composition.components.len(), 2);
+        assert_eq!(composition.components[0].element.symbol, "Cu");
+        assert_close(60.0, composition.percent("Cu"), TOLERANCE);
+        assert_close(40.0, composition.percent("Zn"), TOLERANCE);
+        assert_close(0.0, composition.percent("Fe"), TOLERANCE);
+
+        for notation in [
+            "Ni80Cr20 (wt%)",
+            "Ni80Cr20 wt%",
+            "Ni80Cr20 (WT.%)",
+            "Ni 80 Cr 20",
+            "Ni80-Cr20",
+            "Ni80, Cr20",
+            "nichrome",
+        ] {
+            let composition = parse_composition(notation).unwrap();
+            assert_eq!(composition.basis, CompositionBasis::WeightPercent);
+            assert_close(80.0, composition.percent("Ni"), TOLERANCE);
+            assert_close(20.0, composition.percent("Cr"), TOLERANCE);
+        }
+
+        let composition = parse_composition("Ti50Ni50 (at%)").unwrap();
+        assert_eq!(composition.basis, CompositionBasis::AtomPercent);
+
+        let composition = parse_composition("Stainless  Steel 316").unwrap();
+        assert_close(68.5, composition.percent("Fe"), TOLERANCE);
+        assert_close(2.5, composition.percent("Mo"), TOLERANCE);
+
+        let composition = parse_composition("FeCr18Ni8").unwrap();
+        assert_close(74.0, composition.percent("Fe"), TOLERANCE);
+
+        assert_eq!(
+            parse_composition("").unwrap_err().to_string(),
+            "empty composition"
+        );
+        assert_eq!(
+            parse_composition("Cu60Xy40").unwrap_err().to_string(),
+            "unknown element Xy"
+        );
+        assert_eq!(
+            parse_composition("Cu60Zn30").unwrap_err().to_string(),
+            "total is 90% instead of 100%"
+        );
+        assert_eq!(
+            parse_composition("Cu60Cu40").unwrap_err().to_string(),
+            "duplicate element Cu"
+        );
+        assert!(parse_composition("FeCrNi8").is_err());
+        assert!(parse_composition("Cu0Zn100").is_err());
+        assert!(parse_composition("Cu60.0.1Zn40").is_err());
+        assert!(parse_composition("60Cu40Zn").is_err());
+        assert!(parse_composition("stainless steel 999").is_err());
+    }
+
+    #[test]
+    fn test_convert_composition() {
+        init_logger();
+
+        let composition = parse_composition("Cu60Zn40").unwrap();
+        let atom_percent = composition.to_atom_percent();
+        assert_eq!(atom_percent.basis, CompositionBasis::AtomPercent);
+        assert_close(60.681, atom_percent.percent("Cu"), TOLERANCE);
+        assert_close(39.319, atom_percent.percent("Zn"), TOLERANCE);
+        assert_eq!(atom_percent.to_string(), "Cu60.68Zn39.32 (at%)");
+
+        let weight_percent = atom_percent.to_weight_percent();
+        assert_close(60.0, weight_percent.percent("Cu"), TOLERANCE);
+        assert_eq!(weight_percent.to_string(), "Cu60Zn40 (wt%)");
+
+        let composition = parse_composition("Ti50Ni50 at%").unwrap();
+        assert_close(
+            44.92,
+            composition.to_weight_percent().percent("Ti"),
+            TOLERANCE,
+        );
+        assert_eq!(composition.to_atom_percent(), composition);
+    }
+
+    #[test]
+    fn test_composition_classification() {
+        init_logger();
+
+        let classification = parse_composition("solder").unwrap().classification();
+        assert_eq!(
+            classification.heavy_metals,
+            vec!["Pb".to_string(), "Sn".to_string()]
+        );
+        assert_eq!(
+            classification.metals,
+            vec!["Pb".to_string(), "Sn".to_string()]
+        );
+        assert!(classification.is_inorganic());
+
+        let classification = parse_composition("stainless steel 316")
+            .unwrap()
+            .classification();
+        assert_eq!(
+            classification.heavy_metals,
+            vec!["Cr".to_string(), "Mo".to_string(), "Ni".to_string()]
+        );
+    }
+}
@@ -8,3 +8,9 @@ pub static CE_NUMBER_RE: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|
 });
 pub static ALL_ZERO_RE: std::sync::LazyLock<Regex> =
     std::sync::LazyLock::new(|| Regex::new(r"^0+$").unwrap());
+pub static INDEX_NUMBER_RE: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| {
+    Regex::new(
+        r"^(?P<group1>[0-9]{3})-(?P<group2>[0-9]{3})-(?P<group3>[0-9]{2})-(?P<checkdigit>[0-9]{1})$",
+    )
+    .unwrap()
+});
@@ -0,0 +1,1167 @@
+/// A chemical element of the periodic table.
+#[derive(Debug, PartialEq)]
+pub struct Element {
+    pub atomic_number: u8,
+    pub symbol: &'static str,
+    pub name: &'static str,
+    /// Standard atomic weight in g/mol, or the mass number of the most stable isotope
+    /// for elements without stable isotopes.
+    pub atomic_weight: f64,
+    /// Mass in u of the most abundant isotope, or of the most stable isotope
+    /// for elements without stable isotopes.
+    pub monoisotopic_mass: f64,
+    /// Common oxidation states, in increasing order.
+    pub oxidation_states: &'static [i8],
+}
+
+/// The category of an element in the periodic table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementCategory {
+    AlkaliMetal,
+    AlkalineEarthMetal,
+    TransitionMetal,
+    Lanthanide,
+    Actinide,
+    PostTransitionMetal,
+    Metalloid,
+    Nonmetal,
+    Halogen,
+    NobleGas,
+}
+
+/// Atomic numbers of the last element of each period.
+const PERIOD_ENDS: [u8; 7] = [2, 10, 18, 36, 54, 86, 118];
+
+/// Elements commonly regulated as heavy metals for waste disposal.
+pub const HEAVY_METALS: [&str; 16] = [
+    "As", "Ba", "Cd", "Co", "Cr", "Cu", "Hg", "Mo", "Ni", "Pb", "Sb", "Se", "Sn", "Tl", "V", "Zn",
+];
+
+impl Element {
+    /// Returns the common positive oxidation states of the element.
+    pub fn positive_oxidation_states(&self) -> impl Iterator<Item = i8> {
+        self.oxidation_states
+            .iter()
+            .copied()
+            .filter(|state| *state > 0)
+    }
+
+    /// Returns the category of the element.
+    /// Astatine and tennessine are halogens, polonium and livermorium post-transition metals.
+    #[must_use]
+    pub fn category(&self) -> ElementCategory {
+        match self.atomic_number {
+            3 | 11 | 19 | 37 | 55 | 87 => ElementCategory::AlkaliMetal,
+            4 | 12 | 20 | 38 | 56 | 88 => ElementCategory::AlkalineEarthMetal,
+            2 | 10 | 18 | 36 | 54 | 86 | 118 => ElementCategory::NobleGas,
+            9 | 17 | 35 | 53 | 85 | 117 => ElementCategory::Halogen,
+            5 | 14 | 32 | 33 | 51 | 52 => ElementCategory::Metalloid,
+            1 | 6 | 7 | 8 | 15 | 16 | 34 => ElementCategory::Nonmetal,
+            57..=71 => ElementCategory::Lanthanide,
+            89..=103 => ElementCategory::Actinide,
+            21..=30 | 39..=48 | 72..=80 | 104..=112 => ElementCategory::TransitionMetal,
+            _ => ElementCategory::PostTransitionMetal,
+        }
+    }
+
+    /// Returns true if the element is a metal, metalloids excluded.
+    #[must_use]
+    pub fn is_metal(&self) -> bool {
+        !matches!(
+            self.category(),
+            ElementCategory::Metalloid
+                | ElementCategory::Nonmetal
+                | ElementCategory::Halogen
+                | ElementCategory::NobleGas
+        )
+    }
+
+    /// Returns true if the element is a halogen.
+    #[must_use]
+    pub fn is_halogen(&self) -> bool {
+        self.category() == ElementCategory::Halogen
+    }
+
+    /// Returns true if the element is in `HEAVY_METALS`.
+    #[must_use]
+    pub fn is_heavy_metal(&self) -> bool {
+        HEAVY_METALS.contains(&self.symbol)
+    }
+
+    /// Returns true if the element has no stable isotope.
+    /// Bismuth, with a half-life far longer than the age of the universe, is considered stable.
+    #[must_use]
+    pub fn is_radioactive(&self) -> bool {
+        matches!(self.atomic_number, 43 | 61 | 84..)
+    }
+
+    /// Returns the period of the element.
+    #[must_use]
+    pub fn period(&self) -> u8 {
+        let mut period = 1;
+        for period_end in PERIOD_ENDS {
+            if self.atomic_number <= period_end {
+                break;
+            }
+            period += 1;
+        }
+
+        period
+    }
+
+    /// Returns the IUPAC group of the element, from 1 to 18.
+    /// Lanthanides and actinides, from lanthanum to ytterbium and actinium to nobelium, have no group.
+    #[must_use]
+    pub fn group(&self) -> Option<u8> {
+        let period = self.period();
+        let period_start = match period {
+            1 => 0,
+            _ => PERIOD_ENDS[usize::from(period) - 2],
+        };
+        // Position in the period, from 1.
+        let position = self.atomic_number - period_start;
+
+        match (period, position) {
+            (1, 1) => Some(1),
+            (1, _) => Some(18),
+            (2 | 3, 3..) => Some(position + 10),
+            (2..=5, _) | (_, 1 | 2) => Some(position),
+            (_, 3..=16) => None,
+            _ => Some(position - 14),
+        }
+    }
+}
+
+/// Deuterium is accepted as an atom of its own in formulas, as in `CDCl3`.
+pub static DEUTERIUM: Element = Element {
+    atomic_number: 1,
+    symbol: "D",
+    name: "deuterium",
+    atomic_weight: 2.014,
+    monoisotopic_mass: 2.014_101_778_12,
+    oxidation_states: &[1],
+};
+
+/// The elements of the periodic table ordered by atomic number.
+pub static ELEMENTS: [Element; 118] = [
+    Element {
+        atomic_number: 1,
+        symbol: "H",
+        name: "hydrogen",
+        atomic_weight: 1.008,
+        monoisotopic_mass: 1.007_825_032_23,
+        oxidation_states: &[-1, 1],
+    },
+    Element {
+        atomic_number: 2,
+        symbol: "He",
+        name: "helium",
+        atomic_weight: 4.0026,
+        monoisotopic_mass: 4.002_603_254_13,
+        oxidation_states: &[],
+    },
+    Element {
+        atomic_number: 3,
+        symbol: "Li",
+        name: "lithium",
+        atomic_weight: 6.94,
+        monoisotopic_mass: 7.016_003_436_6,
+        oxidation_states: &[1],
+    },
+    Element {
+        atomic_number: 4,
+        symbol: "Be",
+        name: "beryllium",
+        atomic_weight: 9.0122,
+        monoisotopic_mass: 9.012_183_065,
+        oxidation_states: &[2],
+    },
+    Element {
+        atomic_number: 5,
+        symbol: "B",
+        name: "boron",
+        atomic_weight: 10.81,
+        monoisotopic_mass: 11.009_305_36,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 6,
+        symbol: "C",
+        name: "carbon",
+        atomic_weight: 12.011,
+        monoisotopic_mass: 12.0,
+        oxidation_states: &[-4, 2, 4],
+    },
+    Element {
+        atomic_number: 7,
+        symbol: "N",
+        name: "nitrogen",
+        atomic_weight: 14.007,
+        monoisotopic_mass: 14.003_074_004_43,
+        oxidation_states: &[-3, 3, 5],
+    },
+    Element {
+        atomic_number: 8,
+        symbol: "O",
+        name: "oxygen",
+        atomic_weight: 15.999,
+        monoisotopic_mass: 15.994_914_619_57,
+        oxidation_states: &[-2],
+    },
+    Element {
+        atomic_number: 9,
+        symbol: "F",
+        name: "fluorine",
+        atomic_weight: 18.998,
+        monoisotopic_mass: 18.998_403_162_73,
+        oxidation_states: &[-1],
+    },
+    Element {
+        atomic_number: 10,
+        symbol: "Ne",
+        name: "neon",
+        atomic_weight: 20.180,
+        monoisotopic_mass: 19.992_440_176_2,
+        oxidation_states: &[],
+    },
+    Element {
+        atomic_number: 11,
+        symbol: "Na",
+        name: "sodium",
+        atomic_weight: 22.990,
+        monoisotopic_mass: 22.989_769_282,
+        oxidation_states: &[1],
+    },
+    Element {
+        atomic_number: 12,
+        symbol: "Mg",
+        name: "magnesium",
+        atomic_weight: 24.305,
+        monoisotopic_mass: 23.985_041_697,
+        oxidation_states: &[2],
+    },
+    Element {
+        atomic_number: 13,
+        symbol: "Al",
+        name: "aluminium",
+        atomic_weight: 26.982,
+        monoisotopic_mass: 26.981_538_53,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 14,
+        symbol: "Si",
+        name: "silicon",
+        atomic_weight: 28.085,
+        monoisotopic_mass: 27.976_926_534_65,
+        oxidation_states: &[-4, 4],
+    },
+    Element {
+        atomic_number: 15,
+        symbol: "P",
+        name: "phosphorus",
+        atomic_weight: 30.974,
+        monoisotopic_mass: 30.973_761_998_42,
+        oxidation_states: &[-3, 3, 5],
+    },
+    Element {
+        atomic_number: 16,
+        symbol: "S",
+        name: "sulfur",
+        atomic_weight: 32.06,
+        monoisotopic_mass: 31.972_071_174_4,
+        oxidation_states: &[-2, 2, 4, 6],
+    },
+    Element {
+        atomic_number: 17,
+        symbol: "Cl",
+        name: "chlorine",
+        atomic_weight: 35.45,
+        monoisotopic_mass: 34.968_852_682,
+        oxidation_states: &[-1, 1, 3, 5, 7],
+    },
+    Element {
+        atomic_number: 18,
+        symbol: "Ar",
+        name: "argon",
+        atomic_weight: 39.95,
+        monoisotopic_mass: 39.962_383_123_7,
+        oxidation_states: &[],
+    },
+    Element {
+        atomic_number: 19,
+        symbol: "K",
+        name: "potassium",
+        atomic_weight: 39.098,
+        monoisotopic_mass: 38.963_706_486_4,
+        oxidation_states: &[1],
+    },
+    Element {
+        atomic_number: 20,
+        symbol: "Ca",
+        name: "calcium",
+        atomic_weight: 40.078,
+        monoisotopic_mass: 39.962_590_863,
+        oxidation_states: &[2],
+    },
+    Element {
+        atomic_number: 21,
+        symbol: "Sc",
+        name: "scandium",
+        atomic_weight: 44.956,
+        monoisotopic_mass: 44.955_908_28,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 22,
+        symbol: "Ti",
+        name: "titanium",
+        atomic_weight: 47.867,
+        monoisotopic_mass: 47.947_941_98,
+        oxidation_states: &[2, 3, 4],
+    },
+    Element {
+        atomic_number: 23,
+        symbol: "V",
+        name: "vanadium",
+        atomic_weight: 50.942,
+        monoisotopic_mass: 50.943_957_04,
+        oxidation_states: &[2, 3, 4, 5],
+    },
+    Element {
+        atomic_number: 24,
+        symbol: "Cr",
+        name: "chromium",
+        atomic_weight: 51.996,
+        monoisotopic_mass: 51.940_506_23,
+        oxidation_states: &[2, 3, 6],
+    },
+    Element {
+        atomic_number: 25,
+        symbol: "Mn",
+        name: "manganese",
+        atomic_weight: 54.938,
+        monoisotopic_mass: 54.938_043_91,
+        oxidation_states: &[2, 3, 4, 6, 7],
+    },
+    Element {
+        atomic_number: 26,
+        symbol: "Fe",
+        name: "iron",
+        atomic_weight: 55.845,
+        monoisotopic_mass: 55.934_936_33,
+        oxidation_states: &[2, 3],
+    },
+    Element {
+        atomic_number: 27,
+        symbol: "Co",
+        name: "cobalt",
+        atomic_weight: 58.933,
+        monoisotopic_mass: 58.933_194_29,
+        oxidation_states: &[2, 3],
+    },
+    Element {
+        atomic_number: 28,
+        symbol: "Ni",
+        name: "nickel",
+        atomic_weight: 58.693,
+        monoisotopic_mass: 57.935_342_41,
+        oxidation_states: &[2],
+    },
+    Element {
+        atomic_number: 29,
+        symbol: "Cu",
+        name: "copper",
+        atomic_weight: 63.546,
+        monoisotopic_mass: 62.929_597_72,
+        oxidation_states: &[1, 2],
+    },
+    Element {
+        atomic_number: 30,
+        symbol: "Zn",
+        name: "zinc",
+        atomic_weight: 65.38,
+        monoisotopic_mass: 63.929_142_01,
+        oxidation_states: &[2],
+    },
+    Element {
+        atomic_number: 31,
+        symbol: "Ga",
+        name: "gallium",
+        atomic_weight: 69.723,
+        monoisotopic_mass: 68.925_573_5,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 32,
+        symbol: "Ge",
+        name: "germanium",
+        atomic_weight: 72.630,
+        monoisotopic_mass: 73.921_177_761,
+        oxidation_states: &[-4, 2, 4],
+    },
+    Element {
+        atomic_number: 33,
+        symbol: "As",
+        name: "arsenic",
+        atomic_weight: 74.922,
+        monoisotopic_mass: 74.921_594_57,
+        oxidation_states: &[-3, 3, 5],
+    },
+    Element {
+        atomic_number: 34,
+        symbol: "Se",
+        name: "selenium",
+        atomic_weight: 78.971,
+        monoisotopic_mass: 79.916_521_8,
+        oxidation_states: &[-2, 4, 6],
+    },
+    Element {
+        atomic_number: 35,
+        symbol: "Br",
+        name: "bromine",
+        atomic_weight: 79.904,
+        monoisotopic_mass: 78.918_337_6,
+        oxidation_states: &[-1, 1, 3, 5],
+    },
+    Element {
+        atomic_number: 36,
+        symbol: "Kr",
+        name: "krypton",
+        atomic_weight: 83.798,
+        monoisotopic_mass: 83.911_497_728_2,
+        oxidation_states: &[2],
+    },
+    Element {
+        atomic_number: 37,
+        symbol: "Rb",
+        name: "rubidium",
+        atomic_weight: 85.468,
+        monoisotopic_mass: 84.911_789_737_9,
+        oxidation_states: &[1],
+    },
+    Element {
+        atomic_number: 38,
+        symbol: "Sr",
+        name: "strontium",
+        atomic_weight: 87.62,
+        monoisotopic_mass: 87.905_612_5,
+        oxidation_states: &[2],
+    },
+    Element {
+        atomic_number: 39,
+        symbol: "Y",
+        name: "yttrium",
+        atomic_weight: 88.906,
+        monoisotopic_mass: 88.905_840_3,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 40,
+        symbol: "Zr",
+        name: "zirconium",
+        atomic_weight: 91.224,
+        monoisotopic_mass: 89.904_697_7,
+        oxidation_states: &[4],
+    },
+    Element {
+        atomic_number: 41,
+        symbol: "Nb",
+        name: "niobium",
+        atomic_weight: 92.906,
+        monoisotopic_mass: 92.906_373,
+        oxidation_states: &[3, 5],
+    },
+    Element {
+        atomic_number: 42,
+        symbol: "Mo",
+        name: "molybdenum",
+        atomic_weight: 95.95,
+        monoisotopic_mass: 97.905_404_82,
+        oxidation_states: &[4, 6],
+    },
+    Element {
+        atomic_number: 43,
+        symbol: "Tc",
+        name: "technetium",
+        atomic_weight: 98.0,
+        monoisotopic_mass: 97.907_212_4,
+        oxidation_states: &[4, 7],
+    },
+    Element {
+        atomic_number: 44,
+        symbol: "Ru",
+        name: "ruthenium",
+        atomic_weight: 101.07,
+        monoisotopic_mass: 101.904_344_1,
+        oxidation_states: &[3, 4],
+    },
+    Element {
+        atomic_number: 45,
+        symbol: "Rh",
+        name: "rhodium",
+        atomic_weight: 102.91,
+        monoisotopic_mass: 102.905_498,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 46,
+        symbol: "Pd",
+        name: "palladium",
+        atomic_weight: 106.42,
+        monoisotopic_mass: 105.903_480_4,
+        oxidation_states: &[2, 4],
+    },
+    Element {
+        atomic_number: 47,
+        symbol: "Ag",
+        name: "silver",
+        atomic_weight: 107.87,
+        monoisotopic_mass: 106.905_091_6,
+        oxidation_states: &[1],
+    },
+    Element {
+        atomic_number: 48,
+        symbol: "Cd",
+        name: "cadmium",
+        atomic_weight: 112.41,
+        monoisotopic_mass: 113.903_365_09,
+        oxidation_states: &[2],
+    },
+    Element {
+        atomic_number: 49,
+        symbol: "In",
+        name: "indium",
+        atomic_weight: 114.82,
+        monoisotopic_mass: 114.903_878_776,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 50,
+        symbol: "Sn",
+        name: "tin",
+        atomic_weight: 118.71,
+        monoisotopic_mass: 119.902_201_63,
+        oxidation_states: &[2, 4],
+    },
+    Element {
+        atomic_number: 51,
+        symbol: "Sb",
+        name: "antimony",
+        atomic_weight: 121.76,
+        monoisotopic_mass: 120.903_812,
+        oxidation_states: &[-3, 3, 5],
+    },
+    Element {
+        atomic_number: 52,
+        symbol: "Te",
+        name: "tellurium",
+        atomic_weight: 127.60,
+        monoisotopic_mass: 129.906_222_748,
+        oxidation_states: &[-2, 4, 6],
+    },
+    Element {
+        atomic_number: 53,
+        symbol: "I",
+        name: "iodine",
+        atomic_weight: 126.90,
+        monoisotopic_mass: 126.904_471_9,
+        oxidation_states: &[-1, 1, 5, 7],
+    },
+    Element {
+        atomic_number: 54,
+        symbol: "Xe",
+        name: "xenon",
+        atomic_weight: 131.29,
+        monoisotopic_mass: 131.904_155_085_6,
+        oxidation_states: &[2, 4, 6],
+    },
+    Element {
+        atomic_number: 55,
+        symbol: "Cs",
+        name: "caesium",
+        atomic_weight: 132.91,
+        monoisotopic_mass: 132.905_451_961,
+        oxidation_states: &[1],
+    },
+    Element {
+        atomic_number: 56,
+        symbol: "Ba",
+        name: "barium",
+        atomic_weight: 137.33,
+        monoisotopic_mass: 137.905_247,
+        oxidation_states: &[2],
+    },
+    Element {
+        atomic_number: 57,
+        symbol: "La",
+        name: "lanthanum",
+        atomic_weight: 138.91,
+        monoisotopic_mass: 138.906_356_3,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 58,
+        symbol: "Ce",
+        name: "cerium",
+        atomic_weight: 140.12,
+        monoisotopic_mass: 139.905_443_1,
+        oxidation_states: &[3, 4],
+    },
+    Element {
+        atomic_number: 59,
+        symbol: "Pr",
+        name: "praseodymium",
+        atomic_weight: 140.91,
+        monoisotopic_mass: 140.907_657_6,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 60,
+        symbol: "Nd",
+        name: "neodymium",
+        atomic_weight: 144.24,
+        monoisotopic_mass: 141.907_729,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 61,
+        symbol: "Pm",
+        name: "promethium",
+        atomic_weight: 145.0,
+        monoisotopic_mass: 144.912_755_9,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 62,
+        symbol: "Sm",
+        name: "samarium",
+        atomic_weight: 150.36,
+        monoisotopic_mass: 151.919_739_7,
+        oxidation_states: &[2, 3],
+    },
+    Element {
+        atomic_number: 63,
+        symbol: "Eu",
+        name: "europium",
+        atomic_weight: 151.96,
+        monoisotopic_mass: 152.921_238,
+        oxidation_states: &[2, 3],
+    },
+    Element {
+        atomic_number: 64,
+        symbol: "Gd",
+        name: "gadolinium",
+        atomic_weight: 157.25,
+        monoisotopic_mass: 157.924_112_3,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 65,
+        symbol: "Tb",
+        name: "terbium",
+        atomic_weight: 158.93,
+        monoisotopic_mass: 158.925_354_7,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 66,
+        symbol: "Dy",
+        name: "dysprosium",
+        atomic_weight: 162.50,
+        monoisotopic_mass: 163.929_181_9,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 67,
+        symbol: "Ho",
+        name: "holmium",
+        atomic_weight: 164.93,
+        monoisotopic_mass: 164.930_328_8,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 68,
+        symbol: "Er",
+        name: "erbium",
+        atomic_weight: 167.26,
+        monoisotopic_mass: 165.930_299_5,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 69,
+        symbol: "Tm",
+        name: "thulium",
+        atomic_weight: 168.93,
+        monoisotopic_mass: 168.934_217_9,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 70,
+        symbol: "Yb",
+        name: "ytterbium",
+        atomic_weight: 173.05,
+        monoisotopic_mass: 173.938_866_4,
+        oxidation_states: &[2, 3],
+    },
+    Element {
+        atomic_number: 71,
+        symbol: "Lu",
+        name: "lutetium",
+        atomic_weight: 174.97,
+        monoisotopic_mass: 174.940_775_2,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 72,
+        symbol: "Hf",
+        name: "hafnium",
+        atomic_weight: 178.49,
+        monoisotopic_mass: 179.946_557,
+        oxidation_states: &[4],
+    },
+    Element {
+        atomic_number: 73,
+        symbol: "Ta",
+        name: "tantalum",
+        atomic_weight: 180.95,
+        monoisotopic_mass: 180.947_995_8,
+        oxidation_states: &[5],
+    },
+    Element {
+        atomic_number: 74,
+        symbol: "W",
+        name: "tungsten",
+        atomic_weight: 183.84,
+        monoisotopic_mass: 183.950_930_92,
+        oxidation_states: &[4, 6],
+    },
+    Element {
+        atomic_number: 75,
+        symbol: "Re",
+        name: "rhenium",
+        atomic_weight: 186.21,
+        monoisotopic_mass: 186.955_750_1,
+        oxidation_states: &[4, 7],
+    },
+    Element {
+        atomic_number: 76,
+        symbol: "Os",
+        name: "osmium",
+        atomic_weight: 190.23,
+        monoisotopic_mass: 191.961_477,
+        oxidation_states: &[4, 8],
+    },
+    Element {
+        atomic_number: 77,
+        symbol: "Ir",
+        name: "iridium",
+        atomic_weight: 192.22,
+        monoisotopic_mass: 192.962_921_6,
+        oxidation_states: &[3, 4],
+    },
+    Element {
+        atomic_number: 78,
+        symbol: "Pt",
+        name: "platinum",
+        atomic_weight: 195.08,
+        monoisotopic_mass: 194.964_791_7,
+        oxidation_states: &[2, 4],
+    },
+    Element {
+        atomic_number: 79,
+        symbol: "Au",
+        name: "gold",
+        atomic_weight: 196.97,
+        monoisotopic_mass: 196.966_568_79,
+        oxidation_states: &[1, 3],
+    },
+    Element {
+        atomic_number: 80,
+        symbol: "Hg",
+        name: "mercury",
+        atomic_weight: 200.59,
+        monoisotopic_mass: 201.970_643_4,
+        oxidation_states: &[1, 2],
+    },
+    Element {
+        atomic_number: 81,
+        symbol: "Tl",
+        name: "thallium",
+        atomic_weight: 204.38,
+        monoisotopic_mass: 204.974_427_8,
+        oxidation_states: &[1, 3],
+    },
+    Element {
+        atomic_number: 82,
+        symbol: "Pb",
+        name: "lead",
+        atomic_weight: 207.2,
+        monoisotopic_mass: 207.976_652_5,
+        oxidation_states: &[2, 4],
+    },
+    Element {
+        atomic_number: 83,
+        symbol: "Bi",
+        name: "bismuth",
+        atomic_weight: 208.98,
+        monoisotopic_mass: 208.980_399_1,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 84,
+        symbol: "Po",
+        name: "polonium",
+        atomic_weight: 209.0,
+        monoisotopic_mass: 208.982_430_8,
+        oxidation_states: &[-2, 2, 4],
+    },
+    Element {
+        atomic_number: 85,
+        symbol: "At",
+        name: "astatine",
+        atomic_weight: 210.0,
+        monoisotopic_mass: 209.987_147_9,
+        oxidation_states: &[-1, 1],
+    },
+    Element {
+        atomic_number: 86,
+        symbol: "Rn",
+        name: "radon",
+        atomic_weight: 222.0,
+        monoisotopic_mass: 222.017_578_2,
+        oxidation_states: &[2],
+    },
+    Element {
+        atomic_number: 87,
+        symbol: "Fr",
+        name: "francium",
+        atomic_weight: 223.0,
+        monoisotopic_mass: 223.019_736,
+        oxidation_states: &[1],
+    },
+    Element {
+        atomic_number: 88,
+        symbol: "Ra",
+        name: "radium",
+        atomic_weight: 226.0,
+        monoisotopic_mass: 226.025_410_3,
+        oxidation_states: &[2],
+    },
+    Element {
+        atomic_number: 89,
+        symbol: "Ac",
+        name: "actinium",
+        atomic_weight: 227.0,
+        monoisotopic_mass: 227.027_752_3,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 90,
+        symbol: "Th",
+        name: "thorium",
+        atomic_weight: 232.04,
+        monoisotopic_mass: 232.038_055_8,
+        oxidation_states: &[4],
+    },
+    Element {
+        atomic_number: 91,
+        symbol: "Pa",
+        name: "protactinium",
+        atomic_weight: 231.04,
+        monoisotopic_mass: 231.035_884_2,
+        oxidation_states: &[5],
+    },
+    Element {
+        atomic_number: 92,
+        symbol: "U",
+        name: "uranium",
+        atomic_weight: 238.03,
+        monoisotopic_mass: 238.050_788_4,
+        oxidation_states: &[3, 4, 5, 6],
+    },
+    Element {
+        atomic_number: 93,
+        symbol: "Np",
+        name: "neptunium",
+        atomic_weight: 237.0,
+        monoisotopic_mass: 237.048_173_6,
+        oxidation_states: &[3, 4, 5, 6],
+    },
+    Element {
+        atomic_number: 94,
+        symbol: "Pu",
+        name: "plutonium",
+        atomic_weight: 244.0,
+        monoisotopic_mass: 244.064_205_3,
+        oxidation_states: &[3, 4, 5, 6],
+    },
+    Element {
+        atomic_number: 95,
+        symbol: "Am",
+        name: "americium",
+        atomic_weight: 243.0,
+        monoisotopic_mass: 243.061_381_3,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 96,
+        symbol: "Cm",
+        name: "curium",
+        atomic_weight: 247.0,
+        monoisotopic_mass: 247.070_354_1,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 97,
+        symbol: "Bk",
+        name: "berkelium",
+        atomic_weight: 247.0,
+        monoisotopic_mass: 247.070_307_3,
+        oxidation_states: &[3, 4],
+    },
+    Element {
+        atomic_number: 98,
+        symbol: "Cf",
+        name: "californium",
+        atomic_weight: 251.0,
+        monoisotopic_mass: 251.079_588_6,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 99,
+        symbol: "Es",
+        name: "einsteinium",
+        atomic_weight: 252.0,
+        monoisotopic_mass: 252.082_98,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 100,
+        symbol: "Fm",
+        name: "fermium",
+        atomic_weight: 257.0,
+        monoisotopic_mass: 257.095_106_1,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 101,
+        symbol: "Md",
+        name: "mendelevium",
+        atomic_weight: 258.0,
+        monoisotopic_mass: 258.098_431_5,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 102,
+        symbol: "No",
+        name: "nobelium",
+        atomic_weight: 259.0,
+        monoisotopic_mass: 259.101_03,
+        oxidation_states: &[2],
+    },
+    Element {
+        atomic_number: 103,
+        symbol: "Lr",
+        name: "lawrencium",
+        atomic_weight: 266.0,
+        monoisotopic_mass: 266.119_83,
+        oxidation_states: &[3],
+    },
+    Element {
+        atomic_number: 104,
+        symbol: "Rf",
+        name: "rutherfordium",
+        atomic_weight: 267.0,
+        monoisotopic_mass: 267.121_79,
+        oxidation_states: &[4],
+    },
+    Element {
+        atomic_number: 105,
+        symbol: "Db",
+        name: "dubnium",
+        atomic_weight: 268.0,
+        monoisotopic_mass: 268.125_67,
+        oxidation_states: &[5],
+    },
+    Element {
+        atomic_number: 106,
+        symbol: "Sg",
+        name: "seaborgium",
+        atomic_weight: 269.0,
+        monoisotopic_mass: 269.128_63,
+        oxidation_states: &[6],
+    },
+    Element {
+        atomic_number: 107,
+        symbol: "Bh",
+        name: "bohrium",
+        atomic_weight: 270.0,
+        monoisotopic_mass: 270.133_36,
+        oxidation_states: &[7],
+    },
+    Element {
+        atomic_number: 108,
+        symbol: "Hs",
+        name: "hassium",
+        atomic_weight: 269.0,
+        monoisotopic_mass: 269.133_75,
+        oxidation_states: &[8],
+    },
+    Element {
+        atomic_number: 109,
+        symbol: "Mt",
+        name: "meitnerium",
+        atomic_weight: 278.0,
+        monoisotopic_mass: 278.156_31,
+        oxidation_states: &[],
+    },
+    Element {
+        atomic_number: 110,
+        symbol: "Ds",
+        name: "darmstadtium",
+        atomic_weight: 281.0,
+        monoisotopic_mass: 281.164_51,
+        oxidation_states: &[],
+    },
+    Element {
+        atomic_number: 111,
+        symbol: "Rg",
+        name: "roentgenium",
+        atomic_weight: 282.0,
+        monoisotopic_mass: 282.169_12,
+        oxidation_states: &[],
+    },
+    Element {
+        atomic_number: 112,
+        symbol: "Cn",
+        name: "copernicium",
+        atomic_weight: 285.0,
+        monoisotopic_mass: 285.177_12,
+        oxidation_states: &[2],
+    },
+    Element {
+        atomic_number: 113,
+        symbol: "Nh",
+        name: "nihonium",
+        atomic_weight: 286.0,
+        monoisotopic_mass: 286.182_21,
+        oxidation_states: &[],
+    },
+    Element {
+        atomic_number: 114,
+        symbol: "Fl",
+        name: "flerovium",
+        atomic_weight: 289.0,
+        monoisotopic_mass: 289.190_42,
+        oxidation_states: &[],
+    },
+    Element {
+        atomic_number: 115,
+        symbol: "Mc",
+        name: "moscovium",
+        atomic_weight: 290.0,
+        monoisotopic_mass: 290.195_98,
+        oxidation_states: &[],
+    },
+    Element {
+        atomic_number: 116,
+        symbol: "Lv",
+        name: "livermorium",
+        atomic_weight: 293.0,
+        monoisotopic_mass: 293.204_49,
+        oxidation_states: &[],
+    },
+    Element {
+        atomic_number: 117,
+        symbol: "Ts",
+        name: "tennessine",
+        atomic_weight: 294.0,
+        monoisotopic_mass: 294.210_46,
+        oxidation_states: &[],
+    },
+    Element {
+        atomic_number: 118,
+        symbol: "Og",
+        name: "oganesson",
+        atomic_weight: 294.0,
+        monoisotopic_mass: 294.213_92,
+        oxidation_states: &[],
+    },
+];
+
+/// Alternative spellings of element names.
+const ALTERNATIVE_NAMES: [(&str, &str); 4] = [
+    ("aluminum", "aluminium"),
+    ("cesium", "caesium"),
+    ("sulphur", "sulfur"),
+    ("wolfram", "tungsten"),
+];
+
+/// Atomic numbers of the elements by the letters of their symbol, 0 if none.
+/// Indexed by the uppercase letter and the lowercase letter, or 26 for one letter symbols.
+static SYMBOL_TABLE: [[u8; 27]; 26] = build_symbol_table();
+
+/// Builds `SYMBOL_TABLE` at compile time.
+const fn build_symbol_table() -> [[u8; 27]; 26] {
+    let mut table = [[0; 27]; 26];
+
+    let mut index = 0;
+    while index < ELEMENTS.len() {
+        let symbol = ELEMENTS[index].symbol.as_bytes();
+        let second_letter = if symbol.len() > 1 {
+            (symbol[1] - b'a') as usize
+        } else {
+            26
+        };
+        table[(symbol[0] - b'A') as usize][second_letter] = ELEMENTS[index].atomic_number;
+
+        index += 1;
+    }
+
+    table
+}
+
+/// Returns the element with the given symbol.
+/// The symbol is case sensitive.
+#[must_use]
+pub fn element_by_symbol(symbol: &str) -> Option<&'static Element> {
+    match symbol.as_bytes() {
+        [b'D'] => Some(&DEUTERIUM),
+        [first @ b'A'..=b'Z'] => {
+            element_by_atomic_number(SYMBOL_TABLE[usize::from(first - b'A')][26])
+        }
+        [first @ b'A'..=b'Z', second @ b'a'..=b'z'] => element_by_atomic_number(
+            SYMBOL_TABLE[usize::from(first - b'A')][usize::from(second - b'a')],
+        ),
+        _ => None,
+    }
+}
+
+/// Returns the element with the given atomic number.
+#[must_use]
+pub fn element_by_atomic_number(atomic_number: u8) -> Option<&'static Element> {
+    ELEMENTS.get(usize::from(atomic_number).checked_sub(1)?)
+}
+
+/// Returns the element with the given name.
+/// The name is case insensitive and alternative spellings such as aluminum or sulphur are accepted.
+#[must_use]
+pub fn element_by_name(name: &str) -> Option<&'static Element> {
+    let lowercase_name = name.trim().to_lowercase();
+
+    let name = ALTERNATIVE_NAMES
+        .iter()
+        .find(|(alternative_name, _)| *alternative_name == lowercase_name)
+        .map_or(lowercase_name.as_str(), |(_, name)| name);
+
+    if name == DEUTERIUM.name {
+        return Some(&DEUTERIUM);
+    }
+
+    ELEMENTS.iter().find(|element| element.name == name)
+}
+
+#[cfg(test)]
+#[path = "element_tests.rs"]
+mod element_tests;
@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::element::*;
+
+    #[test]
+    fn test_elements_order() {
+        for (index, element) in ELEMENTS.iter().enumerate() {
+            assert_eq!(usize::from(element.atomic_number), index + 1);
+        }
+    }
+
+    #[test]
+    fn test_element_by_symbol() {
+        assert_eq!(element_by_symbol("Fe").unwrap().name, "iron");
+        assert_eq!(element_by_symbol("Og").unwrap().atomic_number, 118);
+        assert_eq!(element_by_symbol("D").unwrap().name, "deuterium");
+        assert!(element_by_symbol("fe").is_none());
+        assert!(element_by_symbol("Xy").is_none());
+        assert!(element_by_symbol("").is_none());
+    }
+
+    #[test]
+    fn test_element_by_atomic_number() {
+        assert_eq!(element_by_atomic_number(1).unwrap().symbol, "H");
+        assert_eq!(element_by_atomic_number(26).unwrap().symbol, "Fe");
+        assert!(element_by_atomic_number(0).is_none());
+        assert!(element_by_atomic_number(119).is_none());
+    }
+
+    #[test]
+    fn test_element_by_name() {
+        assert_eq!(element_by_name("iron").unwrap().symbol, "Fe");
+        assert_eq!(element_by_name(" Sodium ").unwrap().symbol, "Na");
+        assert_eq!(element_by_name("aluminum").unwrap().symbol, "Al");
+        assert_eq!(element_by_name("sulphur").unwrap().symbol, "S");
+        assert_eq!(element_by_name("deuterium").unwrap().symbol, "D");
+        assert!(element_by_name("unobtainium").is_none());
+    }
+
+    #[test]
+    fn test_positive_oxidation_states() {
+        let iron = element_by_symbol("Fe").unwrap();
+        assert_eq!(
+            iron.positive_oxidation_states().collect::<Vec<i8>>(),
+            vec![2, 3]
+        );
+
+        let chlorine = element_by_symbol("Cl").unwrap();
+        assert_eq!(
+            chlorine.positive_oxidation_states().collect::<Vec<i8>>(),
+            vec![1, 3, 5, 7]
+        );
+    }
+
+    #[test]
+    fn test_element_category() {
+        let category = |symbol: &str| element_by_symbol(symbol).unwrap().category();
+
+        assert_eq!(category("Na"), ElementCategory::AlkaliMetal);
+        assert_eq!(category("Ca"), ElementCategory::AlkalineEarthMetal);
+        assert_eq!(category("Fe"), ElementCategory::TransitionMetal);
+        assert_eq!(category("Hg"), ElementCategory::TransitionMetal);
+        assert_eq!(category("Ce"), ElementCategory::Lanthanide);
+        assert_eq!(category("U"), ElementCategory::Actinide);
+        assert_eq!(category("Pb"), ElementCategory::PostTransitionMetal);
+        assert_eq!(category("As"), ElementCategory::Metalloid);
+        assert_eq!(category("C"), ElementCategory::Nonmetal);
+        assert_eq!(category("D"), ElementCategory::Nonmetal);
+        assert_eq!(category("Cl"), ElementCategory::Halogen);
+        assert_eq!(category("Ar"), ElementCategory::NobleGas);
+
+        assert!(element_by_symbol("Fe").unwrap().is_metal());
+        assert!(!element_by_symbol("Si").unwrap().is_metal());
+        assert!(element_by_symbol("I").unwrap().is_halogen());
+        assert!(element_by_symbol("Cd").unwrap().is_heavy_metal());
+        assert!(!element_by_symbol("Fe").unwrap().is_heavy_metal());
+        assert!(element_by_symbol("Tc").unwrap().is_radioactive());
+        assert!(element_by_symbol("Po").unwrap().is_radioactive());
+        assert!(element_by_symbol("U").unwrap().is_radioactive());
+        assert!(!element_by_symbol("Bi").unwrap().is_radioactive());
+        assert!(!element_by_symbol("Pb").unwrap().is_radioactive());
+    }
+
+    #[test]
+    fn test_element_period_and_group() {
+        let period_group = |symbol: &str| {
+            let element = element_by_symbol(symbol).unwrap();
+            (element.period(), element.group())
+        };
+
+        assert_eq!(period_group("H"), (1, Some(1)));
+        assert_eq!(period_group("He"), (1, Some(18)));
+        assert_eq!(period_group("Li"), (2, Some(1)));
+        assert_eq!(period_group("B"), (2, Some(13)));
+        assert_eq!(period_group("Cl"), (3, Some(17)));
+        assert_eq!(period_group("Fe"), (4, Some(8)));
+        assert_eq!(period_group("Xe"), (5, Some(18)));
+        assert_eq!(period_group("Ba"), (6, Some(2)));
+        assert_eq!(period_group("La"), (6, None));
+        assert_eq!(period_group("Lu"), (6, Some(3)));
+        assert_eq!(period_group("Hg"), (6, Some(12)));
+        assert_eq!(period_group("Rn"), (6, Some(18)));
+        assert_eq!(period_group("U"), (7, None));
+        assert_eq!(period_group("Og"), (7, Some(18)));
+    }
+}
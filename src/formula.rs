@@ -2,8 +2,14 @@ use log::debug;
 use std::{
     collections::HashMap,
     error::Error,
-    fmt::{Display, Formatter},
+    fmt::{Display, Formatter, Write},
     num::ParseIntError,
+    sync::LazyLock,
+};
+
+use crate::{
+    element::{DEUTERIUM, ELEMENTS, Element, element_by_symbol},
+    lexer::{TokenKind, tokenize},
 };
 
 #[derive(Debug, PartialEq, Eq)]
@@ -14,6 +20,7 @@ pub enum ToEmpiricalFormulaError {
     NumberAfterUnknowAtom,
     UnexpectedNoneAtomCount(String),
     EmptyFormula,
+    CountOverflow,
 }
 
 impl Display for ToEmpiricalFormulaError {
@@ -31,6 +38,7 @@ impl Display for ToEmpiricalFormulaError {
                 write!(f, "unexpected empty atom_count_map value for key {s}")
             }
             ToEmpiricalFormulaError::EmptyFormula => write!(f, "empty formula"),
+            ToEmpiricalFormulaError::CountOverflow => write!(f, "atom count overflow"),
         }
     }
 }
@@ -39,367 +47,942 @@ impl std::error::Error for ToEmpiricalFormulaError {}
 
 /// Sorts the empirical formula from a string.
 /// Sort order: C and H atoms then the others in alphabetical order.
-/// Example of parsing method:
-/// Cl(CaC2(NaCl)3)2.Na=P
-/// ^^. .. . . .. . .      Cl c=1 d=0
-///   ^ .. . . .. . .      depth=1
-///    ^^. . . .. . .      Ca c=1 d=1
-///      ^ . . .. . .      C  c=2 d=1
-///        ^ . .. . .      depth=2
-///         ^^ .. . .      Na c=1 d=2
-///           ^^. . .      Cl c=1 d=2
-///             ^ . .      for each d>=2 multiply atom by 3; (Na c=3 Cl c=3) depth=1
-///               ^ .      for each d>=1 multiply atom by 2; (Na c=6 Cl c=6 ; Ca=2 C=2) depth=0
-///                 ^      forget any other char
+/// Deuterium is accepted as the `D` symbol and kept apart from hydrogen: `CDCl3` gives `CCl3D`.
+/// See `FormulaParser::parse` for the parsing method.
 pub fn to_empirical_formula(formula: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
-    // A struct to store the atom count and parenthesis depth while parsing the formula.
-    #[derive(Debug)]
-    struct AtomBlock {
-        atom_name: String,
-        parenthesis_depth: isize, // use isize to avoid conversions.
-        count: usize,
-    }
-
-    let periodic_table = HashMap::from([
-        ("Ac", "actinium"),
-        ("Ag", "silver"),
-        ("Al", "aluminium"),
-        ("Am", "americium"),
-        ("Ar", "argon"),
-        ("As", "arsenic"),
-        ("At", "astatine"),
-        ("Au", "gold"),
-        ("B", "boron"),
-        ("Ba", "barium"),
-        ("Be", "berylium"),
-        ("Bh", "bohrium"),
-        ("Bi", "bismuth"),
-        ("Bk", "berkelium"),
-        ("Br", "bromine"),
-        ("C", "carbon"),
-        ("Ca", "calcium"),
-        ("Cd", "cadmium"),
-        ("Ce", "cerium"),
-        ("Cf", "californium"),
-        ("Cl", "chlorine"),
-        ("Cm", "curium"),
-        ("Cn", "copemicium"),
-        ("Co", "cobalt"),
-        ("Cr", "chromium"),
-        ("Cs", "caesium"),
-        ("Cu", "copper"),
-        ("D", "deuterium"),
-        ("Db", "dubnium"),
-        ("Ds", "darmstadtium"),
-        ("Dy", "dysprosium"),
-        ("Er", "erbium"),
-        ("Es", "einsteinium"),
-        ("Eu", "europium"),
-        ("F", "fluorine"),
-        ("Fe", "iron"),
-        ("Fm", "fermium"),
-        ("Fr", "francium"),
-        ("Ga", "gallium"),
-        ("Gd", "gadolinium"),
-        ("Ge", "germanium"),
-        ("H", "hydrogen"),
-        ("He", "helium"),
-        ("Hf", "hafnium"),
-        ("Hg", "mercury"),
-        ("Ho", "holmium"),
-        ("Hs", "hassium"),
-        ("I", "iodine"),
-        ("In", "indium"),
-        ("Ir", "iridium"),
-        ("K", "potassium"),
-        ("Kr", "krypton"),
-        ("La", "lanthanum"),
-        ("Li", "lithium"),
-        ("Lr", "lawrencium"),
-        ("Lu", "lutetium"),
-        ("Md", "mendelevium"),
-        ("Mg", "magnesium"),
-        ("Mn", "manganese"),
-        ("Mo", "molybdenum"),
-        ("Mt", "meitnerium"),
-        ("N", "nitrogen"),
-        ("Na", "sodium"),
-        ("Nb", "niobium"),
-        ("Nd", "neodymium"),
-        ("Ne", "neon"),
-        ("Ni", "nickel"),
-        ("No", "nobelium"),
-        ("Np", "neptunium"),
-        ("O", "oxygen"),
-        ("Os", "osmium"),
-        ("P", "phosphorus"),
-        ("Pa", "protactinium"),
-        ("Pb", "lead"),
-        ("Pd", "palladium"),
-        ("Pm", "promethium"),
-        ("Po", "polonium"),
-        ("Pr", "praseodymium"),
-        ("Pt", "platinium"),
-        ("Pu", "plutonium"),
-        ("Ra", "radium"),
-        ("Rb", "rubidium"),
-        ("Re", "rhenium"),
-        ("Rf", "rutherfordium"),
-        ("Rg", "roentgenium"),
-        ("Rh", "rhodium"),
-        ("Rn", "radon"),
-        ("Ru", "ruthenium"),
-        ("S", "sulfure"),
-        ("Sb", "antimony"),
-        ("Sc", "scandium"),
-        ("Se", "sefeniuo"),
-        ("Sg", "seaborgium"),
-        ("Si", "silicon"),
-        ("Sm", "samarium"),
-        ("Sn", "tin"),
-        ("Sr", "strontium"),
-        ("Ta", "tantalum"),
-        ("Tb", "terbium"),
-        ("Tc", "technetium"),
-        ("Te", "tellurium"),
-        ("Th", "thorium"),
-        ("Ti", "titanium"),
-        ("Tl", "thallium"),
-        ("Tm", "thulium"),
-        ("U", "uranium"),
-        ("V", "vanadium"),
-        ("W", "tungsten"),
-        ("Xe", "xenon"),
-        ("Y", "yltrium"),
-        ("Yb", "ytterbium"),
-        ("Zn", "zinc"),
-        ("Zr", "zirconium"),
-    ]);
-
-    // Rejecting empty formulas.
-    if formula.is_empty() {
-        return Err(Box::new(ToEmpiricalFormulaError::EmptyFormula));
-    }
+    FormulaParser::new().to_empirical_formula(formula)
+}
 
-    // Creating a vec from input for parsing.
-    let formula_vec: Vec<char> = formula.chars().collect();
+/// Sorts the empirical formulas of a batch of formulas, as for bulk imports.
+/// The parser buffers are shared by the whole batch.
+pub fn to_empirical_formulas<'a>(
+    formulas: impl IntoIterator<Item = &'a str>,
+) -> Vec<Result<String, Box<dyn Error + Send + Sync>>> {
+    let mut parser = FormulaParser::new();
+
+    formulas
+        .into_iter()
+        .map(|formula| parser.to_empirical_formula(formula))
+        .collect()
+}
 
-    let mut atom_blocks: Vec<AtomBlock> = Vec::new();
+/// Counts the atoms of a formula.
+/// The counts are for `AtomCounts::formula_units` formula units, divide them for one formula unit.
+/// See `FormulaParser::parse` for the parsing method.
+pub fn to_atom_counts(formula: &str) -> Result<AtomCounts, Box<dyn Error + Send + Sync>> {
+    FormulaParser::new().parse(formula)
+}
 
-    // Cursor index while parsing the formula.
-    let mut cursor_index = 0;
-    // Current parenthesis depth.
-    let mut parenthesis_depth: isize = 0;
-    // Char under cursor.
-    let mut current_char: char;
-    // Current char at the previous loop
-    let mut previous_char: Option<char> = None;
-    // Possible char after current char.
-    let mut maybe_next_char: Option<char>;
+/// Number of `AtomCounts` slots: deuterium and the elements by atomic number.
+const ATOM_COUNT_SLOTS: usize = ELEMENTS.len() + 1;
+
+/// The `AtomCounts` slots in empirical formula order: C, H and then the symbols in alphabetical order.
+static EMPIRICAL_FORMULA_ORDER: LazyLock<[usize; ATOM_COUNT_SLOTS]> = LazyLock::new(|| {
+    let mut order: [usize; ATOM_COUNT_SLOTS] = std::array::from_fn(|slot| slot);
+    order.sort_by_key(|slot| {
+        let symbol = AtomCounts::element(*slot).symbol;
+        match symbol {
+            "C" => (0, symbol),
+            "H" => (1, symbol),
+            _ => (2, symbol),
+        }
+    });
+
+    order
+});
+
+/// The atom counts of a formula, without allocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtomCounts {
+    /// Deuterium at 0, then the elements by atomic number.
+    counts: [usize; ATOM_COUNT_SLOTS],
+    /// See `formula_units`.
+    formula_units: usize,
+    /// The number of units of the first adduct part counted, 4 for `4CaSO4·2H2O`.
+    first_part_units: usize,
+}
 
-    // Parsing the formula.
-    while cursor_index < formula_vec.len() {
-        // Reading the first char under the cursor.
-        current_char = formula_vec[cursor_index];
+impl Default for AtomCounts {
+    fn default() -> Self {
+        AtomCounts {
+            counts: [0; ATOM_COUNT_SLOTS],
+            formula_units: 1,
+            first_part_units: 1,
+        }
+    }
+}
 
-        // And the next if we are not at the end of the formula.
-        if cursor_index < formula_vec.len() - 1 {
-            maybe_next_char = Some(formula_vec[cursor_index + 1]);
+impl AtomCounts {
+    fn slot(element: &Element) -> usize {
+        if element.symbol == DEUTERIUM.symbol {
+            0
         } else {
-            maybe_next_char = None;
+            usize::from(element.atomic_number)
         }
+    }
 
-        debug!(
-            "current_char: {current_char} maybe_next_char: {maybe_next_char:?}"
-        );
+    fn element(slot: usize) -> &'static Element {
+        match slot {
+            0 => &DEUTERIUM,
+            _ => &ELEMENTS[slot - 1],
+        }
+    }
 
-        match current_char {
-            '(' | '[' => {
-                // Opening block, increase depth and increment cursor.
-                parenthesis_depth += 1;
-                cursor_index += 1;
-                debug!("parenthesis_depth: {parenthesis_depth}");
-            }
-            ')' | ']' => {
-                // Closing block, decrease depth and decrement cursor.
-                parenthesis_depth -= 1;
-                // Check wrong parenthesis number.
-                if parenthesis_depth < 0 {
-                    return Err(Box::new(ToEmpiricalFormulaError::UnbalancedParenthesis));
-                }
+    /// Returns the count of an element, 0 if absent.
+    #[must_use]
+    pub fn get(&self, element: &Element) -> usize {
+        self.counts[Self::slot(element)]
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.counts.iter().all(|count| *count == 0)
+    }
+
+    /// Returns the number of formula units counted.
+    /// Decimal adduct multipliers are scaled to integer counts:
+    /// `CaSO4·0.5H2O` is counted as `Ca2H2O9S2`, 2 formula units.
+    #[must_use]
+    pub fn formula_units(&self) -> usize {
+        self.formula_units
+    }
 
-                cursor_index += 1;
-                debug!("parenthesis_depth: {parenthesis_depth}");
+    /// Iterates over the present elements in empirical formula order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static Element, usize)> + '_ {
+        EMPIRICAL_FORMULA_ORDER
+            .iter()
+            .filter(|slot| self.counts[**slot] > 0)
+            .map(|slot| (Self::element(*slot), self.counts[*slot]))
+    }
+
+    /// Writes the empirical formula into `output`.
+    /// Sort order: C and H atoms then the others in alphabetical order.
+    pub fn write_empirical_formula(&self, output: &mut String) {
+        for (element, count) in self.iter() {
+            output.push_str(element.symbol);
+            if count != 1 {
+                // Writing into a String never fails.
+                let _ = write!(output, "{count}");
             }
-            'A'..='Z' => {
-                // Building the atom search string.
-                let mut maybe_search_atom: Option<String> = None;
-
-                // Beginning of an atom.
-                // 1. Two chars atom?
-                if let Some(next_char) = maybe_next_char
-                    && next_char.is_ascii_lowercase()
+        }
+    }
+
+    /// Returns the molar mass in g/mol of one formula unit.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn molar_mass(&self) -> f64 {
+        self.iter()
+            .map(|(element, count)| element.atomic_weight * count as f64)
+            .sum::<f64>()
+            / self.formula_units as f64
+    }
+
+    /// Returns the monoisotopic mass in u of one formula unit, from the most abundant isotopes.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn monoisotopic_mass(&self) -> f64 {
+        self.iter()
+            .map(|(element, count)| element.monoisotopic_mass * count as f64)
+            .sum::<f64>()
+            / self.formula_units as f64
+    }
+
+    /// Returns the atom counts by symbol, for `formula_units` formula units.
+    #[must_use]
+    pub fn to_hash_map(&self) -> HashMap<String, usize> {
+        self.iter()
+            .map(|(element, count)| (element.symbol.to_string(), count))
+            .collect()
+    }
+}
+
+/// A reusable formula parser.
+/// The buffers are kept between calls, parsing does not allocate once they are large enough.
+#[derive(Debug, Default)]
+pub struct FormulaParser {
+    /// The parsed atoms as (`AtomCounts` slot, count).
+    atoms: Vec<(usize, usize)>,
+    /// The atoms index at each open bracket.
+    group_starts: Vec<usize>,
+    /// The adduct parts as (atoms index, multiplier numerator, multiplier denominator).
+    parts: Vec<(usize, usize, usize)>,
+}
+
+impl FormulaParser {
+    #[must_use]
+    pub fn new() -> Self {
+        FormulaParser::default()
+    }
+
+    /// Counts the atoms of a formula.
+    /// The formula is read with `tokenize`:
+    /// - a count after an atom multiplies the atom, `H2`
+    /// - a count after a closing bracket multiplies the bracket group, `(CH2)4`
+    /// - a multiplier after an adduct separator multiplies the adduct part, `CuSO4·5H2O`
+    /// - the leading multiplier of an adduct multiplies its first part, `2CaSO4·H2O`
+    /// - decimal multipliers are scaled to integer counts, see `AtomCounts::formula_units`
+    /// - an adduct part with a variable multiplier, as `xH2O`, is dropped: its amount is unknown
+    /// - leading multipliers of formulas without adduct, bonds, charges, whitespaces and other chars
+    ///   are ignored
+    /// - unknown atoms, unparsable numbers, unbalanced brackets and counts overflowing `usize` are errors
+    pub fn parse(&mut self, formula: &str) -> Result<AtomCounts, Box<dyn Error + Send + Sync>> {
+        // Rejecting empty formulas.
+        if formula.is_empty() {
+            return Err(Box::new(ToEmpiricalFormulaError::EmptyFormula));
+        }
+
+        self.atoms.clear();
+        self.group_starts.clear();
+        self.parts.clear();
+        self.parts.push((0, 1, 1));
+
+        // Start of the last closed bracket group.
+        let mut last_group_start = 0;
+        let mut previous_kind: Option<TokenKind> = None;
+
+        for token in tokenize(formula) {
+            match token.kind {
+                TokenKind::Element(element) => {
+                    self.atoms.push((AtomCounts::slot(element), 1));
+                }
+                TokenKind::Count(count) => match previous_kind {
+                    Some(TokenKind::Element(_)) => {
+                        if let Some(last_atom) = self.atoms.last_mut() {
+                            last_atom.1 = count;
+                        }
+                    }
+                    Some(TokenKind::CloseBracket(_)) => {
+                        for atom in &mut self.atoms[last_group_start..] {
+                            atom.1 = atom
+                                .1
+                                .checked_mul(count)
+                                .ok_or(ToEmpiricalFormulaError::CountOverflow)?;
+                        }
+                    }
+                    _ => (),
+                },
+                TokenKind::Multiplier(multiplier) => {
+                    if let Some(part) = self.parts.last_mut() {
+                        part.1 = multiplier;
+                    }
+                }
+                TokenKind::FractionalMultiplier(_) => {
+                    // As a fraction: 0.25 is 25/100 reduced to 1/4.
+                    let (integer_part, decimal_part) =
+                        token.text.split_once('.').unwrap_or((token.text, ""));
+                    let denominator = u32::try_from(decimal_part.len())
+                        .ok()
+                        .and_then(|exponent| 10_usize.checked_pow(exponent))
+                        .ok_or(ToEmpiricalFormulaError::CountOverflow)?;
+                    let decimal_value = decimal_part
+                        .parse::<usize>()
+                        .map_err(ToEmpiricalFormulaError::CanNotParseNumber)?;
+                    let numerator = integer_part
+                        .parse::<usize>()
+                        .map_err(ToEmpiricalFormulaError::CanNotParseNumber)?
+                        .checked_mul(denominator)
+                        .and_then(|numerator| numerator.checked_add(decimal_value))
+                        .ok_or(ToEmpiricalFormulaError::CountOverflow)?;
+                    let divisor = gcd(numerator, denominator);
+
+                    if let Some(part) = self.parts.last_mut() {
+                        part.1 = numerator / divisor;
+                        part.2 = denominator / divisor;
+                    }
+                }
+                TokenKind::AdductSeparator => self.parts.push((self.atoms.len(), 1, 1)),
+                TokenKind::OpenBracket(_) => self.group_starts.push(self.atoms.len()),
+                TokenKind::CloseBracket(_) => {
+                    let Some(group_start) = self.group_starts.pop() else {
+                        return Err(Box::new(ToEmpiricalFormulaError::UnbalancedParenthesis));
+                    };
+                    last_group_start = group_start;
+                }
+                TokenKind::Unknown
+                    if VARIABLE_COUNTS.contains(&token.text)
+                        && self.parts.len() > 1
+                        && self
+                            .parts
+                            .last()
+                            .is_some_and(|(start, _, _)| *start == self.atoms.len()) =>
                 {
-                    maybe_search_atom = Some(format!("{current_char}{next_char}"));
+                    if let Some(part) = self.parts.last_mut() {
+                        part.1 = 0;
+                    }
                 }
-                // 2. One char atom.
-                if maybe_search_atom.is_none() {
-                    maybe_search_atom = Some(format!("{current_char}"));
+                TokenKind::Unknown => {
+                    if token.text.starts_with(|c: char| c.is_ascii_uppercase()) {
+                        return Err(Box::new(ToEmpiricalFormulaError::UnknowAtom(
+                            token.text.to_string(),
+                        )));
+                    }
+                    if let Err(e) = token.text.parse::<usize>()
+                        && token.text.starts_with(|c: char| c.is_ascii_digit())
+                    {
+                        return Err(Box::new(ToEmpiricalFormulaError::CanNotParseNumber(e)));
+                    }
                 }
+                _ => (),
+            }
 
-                // We can unwrap safely.
-                let search_atom = maybe_search_atom.unwrap();
-
-                // Does the atom exists?
-                if periodic_table.contains_key(&search_atom.as_str()) {
-                    atom_blocks.push(AtomBlock {
-                        atom_name: search_atom.clone(),
-                        parenthesis_depth,
-                        count: 1,
-                    });
-                    debug!("found atom: {search_atom}");
-                } else {
-                    return Err(Box::new(ToEmpiricalFormulaError::UnknowAtom(search_atom)));
-                }
+            previous_kind = Some(token.kind);
+        }
+
+        if !self.group_starts.is_empty() {
+            return Err(Box::new(ToEmpiricalFormulaError::UnbalancedParenthesis));
+        }
+
+        // Ignoring the leading multiplier of a formula without adduct: 2NaCl.
+        if let [part] = self.parts.as_mut_slice() {
+            part.1 = 1;
+            part.2 = 1;
+        }
 
-                // Updating the cursor.
-                cursor_index += search_atom.len();
+        // Multiplying the adduct parts, by the common denominator of the multipliers.
+        let formula_units = self
+            .parts
+            .iter()
+            .try_fold(1_usize, |lcm, (_, _, denominator)| {
+                (lcm / gcd(lcm, *denominator)).checked_mul(*denominator)
+            })
+            .ok_or(ToEmpiricalFormulaError::CountOverflow)?;
+
+        for (index, (start, numerator, denominator)) in self.parts.iter().enumerate() {
+            let end = self
+                .parts
+                .get(index + 1)
+                .map_or(self.atoms.len(), |(next_start, _, _)| *next_start);
+            let multiplier = formula_units
+                .checked_div(*denominator)
+                .and_then(|factor| numerator.checked_mul(factor))
+                .ok_or(ToEmpiricalFormulaError::CountOverflow)?;
+
+            for atom in &mut self.atoms[*start..end] {
+                atom.1 = atom
+                    .1
+                    .checked_mul(multiplier)
+                    .ok_or(ToEmpiricalFormulaError::CountOverflow)?;
             }
+        }
 
-            '0'..='9' => {
-                // Building the count search string.
-                let mut maybe_count_string: Option<String> = None;
+        let (_, first_numerator, first_denominator) = self.parts[0];
+        let first_part_units = (formula_units / first_denominator)
+            .checked_mul(first_numerator)
+            .filter(|units| *units > 0)
+            .unwrap_or(formula_units);
+
+        let mut atom_counts = AtomCounts {
+            formula_units,
+            first_part_units,
+            ..AtomCounts::default()
+        };
+        for (slot, count) in &self.atoms {
+            atom_counts.counts[*slot] = atom_counts.counts[*slot]
+                .checked_add(*count)
+                .ok_or(ToEmpiricalFormulaError::CountOverflow)?;
+        }
 
-                // Beginning a number.
-                // 1. two digit number?
-                // FIXME: we assume that the number of atoms is < 100.
-                if let Some(next_char) = maybe_next_char
-                    && next_char.is_ascii_digit()
-                {
-                    maybe_count_string = Some(format!("{current_char}{next_char}"));
-                }
+        debug!("atom_counts: {:?}", atom_counts.iter().collect::<Vec<_>>());
 
-                // One digit number.
-                if maybe_count_string.is_none() {
-                    maybe_count_string = Some(format!("{current_char}"));
-                }
+        Ok(atom_counts)
+    }
 
-                // We can unwrap safely.
-                let count_string = maybe_count_string.unwrap();
+    /// Sorts the empirical formula from a string.
+    /// See `to_empirical_formula`.
+    pub fn to_empirical_formula(
+        &mut self,
+        formula: &str,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let atom_counts = self.parse(formula)?;
 
-                // Converting into usize.
-                let count = match count_string.parse::<usize>() {
-                    Ok(count) => Some(count),
-                    Err(e) => {
-                        return Err(Box::new(ToEmpiricalFormulaError::CanNotParseNumber(e)));
-                    }
-                };
-                debug!("count: {count:?}");
-
-                // The count can be for an atom or a closing parenthesis.
-                match previous_char {
-                    Some(')' | ']') => {
-                        // The count if for a parenthesis block.
-                        // For each atom_count with a parenthesis_depth > parenthesis_depth, multiplying the count.
-                        for atom in &mut atom_blocks {
-                            debug!("atom: {atom:?}");
-
-                            if atom.parenthesis_depth > parenthesis_depth {
-                                atom.count *= count.unwrap();
-                                debug!(
-                                    "updating atom count for {}: {}",
-                                    atom.atom_name, atom.count
-                                );
-                            }
-                        }
+        let mut final_formula = String::with_capacity(formula.len());
+        atom_counts.write_empirical_formula(&mut final_formula);
+        debug!("final_formula: {final_formula}");
+
+        Ok(final_formula)
+    }
+}
+
+/// Builds the empirical formula from atom counts.
+/// Sort order: C and H atoms then the others in alphabetical order.
+#[must_use]
+pub fn empirical_formula_from_atom_counts<S: std::hash::BuildHasher>(
+    atom_count_map: &HashMap<String, usize, S>,
+) -> String {
+    // C, H and then in alphabetical order.
+    let mut sorted_atoms: Vec<(&String, &usize)> = atom_count_map.iter().collect();
+    sorted_atoms.sort_by_key(|(atom_name, _)| match atom_name.as_str() {
+        "C" => (0, atom_name.as_str()),
+        "H" => (1, atom_name.as_str()),
+        _ => (2, atom_name.as_str()),
+    });
+
+    let mut final_formula: String = String::new();
+
+    for (atom_name, atom_count) in sorted_atoms {
+        final_formula.push_str(atom_name);
+        if *atom_count != 1 {
+            final_formula.push_str(&atom_count.to_string());
+        }
+    }
+
+    final_formula
+}
+
+/// Greatest common divisor.
+pub(crate) fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Returns the molar mass of a formula in g/mol.
+pub fn molar_mass(formula: &str) -> Result<f64, Box<dyn Error + Send + Sync>> {
+    let atom_counts = FormulaParser::new().parse(formula)?;
+
+    Ok(atom_counts.molar_mass())
+}
+
+/// Returns the monoisotopic (exact) mass of a formula in u.
+/// The charge is ignored: this is the mass of the neutral atoms, see `adduct` for m/z values.
+pub fn monoisotopic_mass(formula: &str) -> Result<f64, Box<dyn Error + Send + Sync>> {
+    let atom_counts = FormulaParser::new().parse(formula)?;
+
+    Ok(atom_counts.monoisotopic_mass())
+}
+
+/// Returns the molar mass in g/mol from atom counts.
+pub fn molar_mass_from_atom_counts<S: std::hash::BuildHasher>(
+    atom_count_map: &HashMap<String, usize, S>,
+) -> Result<f64, Box<dyn Error + Send + Sync>> {
+    let mut total = 0.0;
+
+    for (atom_name, atom_count) in atom_count_map {
+        let Some(element) = element_by_symbol(atom_name) else {
+            return Err(Box::new(ToEmpiricalFormulaError::UnknowAtom(
+                atom_name.clone(),
+            )));
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let atom_count = *atom_count as f64;
+        total += element.atomic_weight * atom_count;
+    }
+
+    debug!("molar_mass: {total}");
+
+    Ok(total)
+}
+
+/// Splits the charge from the end of a formula.
+/// Accepted notations: `NH4+`, `SO4--`, `SO4^2-`, `SO4 2-`, `SO4(2-)` and `[Fe(CN)6]4-`.
+/// Digits directly followed by a sign are atom counts: `SO42-` is read as S O42 with a -1 charge,
+/// except after a single element symbol where they are the charge: `Fe3+` is Fe with a +3 charge
+/// and `O2-` the oxide ion.
+/// Returns the formula without its charge and the charge, 0 if there is no charge.
+#[must_use]
+pub fn split_formula_charge(formula: &str) -> (&str, isize) {
+    let trimmed_formula = formula.trim_end();
+
+    // Charge in parenthesis: SO4(2-).
+    if let Some(rest) = trimmed_formula.strip_suffix(')')
+        && let Some(open_index) = rest.rfind('(')
+        && let Some(charge) = parse_charge(&rest[open_index + 1..])
+    {
+        return (rest[..open_index].trim_end(), charge);
+    }
+
+    let sign = match trimmed_formula.chars().last() {
+        Some('+') => 1,
+        Some('-') => -1,
+        _ => return (formula, 0),
+    };
+
+    // Repeated signs: SO4--.
+    let without_signs = trimmed_formula.trim_end_matches(['+', '-']);
+    let sign_count = trimmed_formula.len() - without_signs.len();
+    if sign_count > 1 {
+        return (without_signs.trim_end(), sign * sign_count.cast_signed());
+    }
+
+    // Charge magnitude separated from the formula: SO4^2-, SO4 2- or [Fe(CN)6]4-.
+    let without_digits = without_signs.trim_end_matches(|c: char| c.is_ascii_digit());
+    if let Ok(magnitude) = without_signs[without_digits.len()..].parse::<isize>() {
+        if let Some(rest) = without_digits.strip_suffix(['^', ' ']) {
+            return (rest.trim_end(), sign * magnitude);
+        }
+        if without_digits.ends_with(']') {
+            return (without_digits, sign * magnitude);
+        }
+        // Monatomic ion: Fe3+.
+        if magnitude > 0 && element_by_symbol(without_digits).is_some() {
+            return (without_digits, sign * magnitude);
+        }
+    }
+
+    (
+        without_signs
+            .strip_suffix('^')
+            .unwrap_or(without_signs)
+            .trim_end(),
+        sign,
+    )
+}
+
+/// Parses a charge such as 2-, +2, + or -.
+fn parse_charge(charge: &str) -> Option<isize> {
+    let (magnitude, sign) = if let Some(magnitude) = charge.strip_suffix('+') {
+        (magnitude, 1)
+    } else if let Some(magnitude) = charge.strip_suffix('-') {
+        (magnitude, -1)
+    } else if let Some(magnitude) = charge.strip_prefix('+') {
+        (magnitude, 1)
+    } else if let Some(magnitude) = charge.strip_prefix('-') {
+        (magnitude, -1)
+    } else {
+        return None;
+    };
+
+    if magnitude.is_empty() {
+        return Some(sign);
+    }
+
+    magnitude
+        .parse::<isize>()
+        .ok()
+        .filter(|magnitude| *magnitude > 0)
+        .map(|magnitude| sign * magnitude)
+}
+
+/// Options of `to_canonical_key`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CanonicalKeyOptions {
+    /// Keeps the charge in the key, `NH4+` and `NH4` have different keys.
+    pub keep_charge: bool,
+    /// Keeps the deuterium in the key, `CDCl3` and `CHCl3` have different keys.
+    /// Deuterium is the only isotope read in formulas: `13CH4` and `[13C]H4` have the key `CH4`.
+    pub keep_deuterium: bool,
+}
+
+/// Returns a key of the composition of a formula, independent of how it was written.
+/// `C2H5OH`, `CH3CH2OH`, `C2H6O` and `OC2H6` have the same key `C2H6O`.
+/// The key is the empirical formula followed by the charge if kept, as in `H4N^+` or `O4S^2-`.
+/// Adducts are counted for their smallest whole number of formula units:
+/// `CaSO4·0.5H2O` and `2CaSO4·H2O` have the same key `H2Ca2O9S2`.
+pub fn to_canonical_key(
+    formula: &str,
+    options: CanonicalKeyOptions,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let (formula_without_charge, charge) = split_formula_charge(formula);
+
+    let atom_counts = to_atom_counts(formula_without_charge)?;
+
+    if atom_counts.is_empty() {
+        return Err(Box::new(ToEmpiricalFormulaError::EmptyFormula));
+    }
+
+    // Reducing the counts of several units of the first adduct part: 4CaSO4·2H2O is counted for 4,
+    // CaSO4·0.5H2O for 2.
+    let divisor = atom_counts
+        .iter()
+        .fold(atom_counts.first_part_units, |divisor, (_, count)| {
+            gcd(divisor, count)
+        });
+    let mut atom_count_map: HashMap<String, usize> = atom_counts
+        .iter()
+        .map(|(element, count)| (element.symbol.to_string(), count / divisor))
+        .collect();
+
+    if !options.keep_deuterium
+        && let Some(deuterium_count) = atom_count_map.remove("D")
+    {
+        *atom_count_map.entry("H".to_string()).or_insert(0) += deuterium_count;
+    }
+
+    let mut key = empirical_formula_from_atom_counts(&atom_count_map);
+
+    if options.keep_charge && charge != 0 {
+        key.push('^');
+        if charge.abs() > 1 {
+            key.push_str(&charge.abs().to_string());
+        }
+        key.push(if charge > 0 { '+' } else { '-' });
+    }
+
+    debug!("key: {key}");
+
+    Ok(key)
+}
+
+/// Returns a stable 64 bits hash of the canonical key of a formula.
+/// The FNV-1a hash is used so that the value does not change across Rust versions or platforms
+/// and can be stored.
+pub fn to_canonical_hash(
+    formula: &str,
+    options: CanonicalKeyOptions,
+) -> Result<u64, Box<dyn Error + Send + Sync>> {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let key = to_canonical_key(formula, options)?;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in key.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+
+    Ok(hash)
+}
+
+/// Atoms of organic chemistry.
+/// Used to rank the suggestions of `recover_formula_case`.
+const ORGANIC_ATOMS: [&str; 10] = ["Br", "C", "Cl", "F", "H", "I", "N", "O", "P", "S"];
+
+/// Other atoms often found in the products of a chemical inventory.
+/// Used to rank the suggestions of `recover_formula_case`.
+const COMMON_ATOMS: [&str; 20] = [
+    "Ag", "Al", "B", "Ba", "Ca", "Co", "Cr", "Cu", "Fe", "Hg", "K", "Li", "Mg", "Mn", "Na", "Ni",
+    "Pb", "Si", "Sn", "Zn",
+];
+
+/// Maximum number of segmentations explored for a single run of letters.
+const MAX_SEGMENTATIONS: usize = 256;
+
+/// Maximum number of suggestions returned by `recover_formula_case`.
+const MAX_FORMULA_SUGGESTIONS: usize = 64;
+
+/// A formula proposed by `recover_formula_case`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormulaSuggestion {
+    /// The input formula with the letters recased.
+    pub formula: String,
+    /// The empirical formula of the suggestion.
+    pub empirical_formula: String,
+    /// The plausibility of the suggestion, the higher the better.
+    pub score: isize,
+}
+
+/// Returns the valid element segmentations of a miscased formula, ranked by plausibility.
+/// Only the letters case is changed, numbers and other chars are kept as is.
+/// Example: `nacl` -> `[NaCl]`, `co` -> `[CO, Co]`, `CO` -> `[CO, Co]`
+/// The score favours the atoms in `ORGANIC_ATOMS` then in `COMMON_ATOMS`
+/// and, if the input has mixed case, the letters whose case already matches the input.
+pub fn recover_formula_case(
+    formula: &str,
+) -> Result<Vec<FormulaSuggestion>, Box<dyn Error + Send + Sync>> {
+    // Rejecting empty formulas.
+    if formula.is_empty() {
+        return Err(Box::new(ToEmpiricalFormulaError::EmptyFormula));
+    }
+
+    // The case of the input is meaningful only if it mixes upper and lower case letters.
+    let is_mixed_case = formula.chars().any(|c| c.is_ascii_uppercase())
+        && formula.chars().any(|c| c.is_ascii_lowercase());
+
+    // Candidate formulas with their score, built run of letters after run of letters.
+    let mut candidates: Vec<(String, isize)> = vec![(String::new(), 0)];
+
+    let mut chars = formula.char_indices().peekable();
+    while let Some((start_index, current_char)) = chars.next() {
+        if !current_char.is_ascii_alphabetic() {
+            // Keeping other chars as is.
+            for (candidate, _) in &mut candidates {
+                candidate.push(current_char);
+            }
+            continue;
+        }
+
+        // Finding the end of the run of letters.
+        let mut end_index = start_index + 1;
+        while let Some((index, next_char)) = chars.peek()
+            && next_char.is_ascii_alphabetic()
+        {
+            end_index = index + 1;
+            chars.next();
+        }
+
+        let letters = &formula[start_index..end_index];
+        let segmentations = segment_letters(letters);
+        debug!("letters: {letters} segmentations: {segmentations:?}");
+
+        if segmentations.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut new_candidates: Vec<(String, isize)> = Vec::new();
+        for (candidate, candidate_score) in &candidates {
+            for segmentation in &segmentations {
+                let mut new_candidate = candidate.clone();
+                let mut score = *candidate_score;
+
+                for atom in segmentation {
+                    new_candidate.push_str(atom);
+                    if ORGANIC_ATOMS.contains(&atom.as_str()) {
+                        score += 3;
+                    } else if COMMON_ATOMS.contains(&atom.as_str()) {
+                        score += 2;
+                    } else {
+                        score -= 2;
                     }
-                    Some('a'..='a' | 'A'..='Z') => {
-                        // The count is for the last atom of atom_blocks.
-                        // Updating the entry.
-                        if let Some(last_atom_count) = atom_blocks.last_mut() {
-                            last_atom_count.count = count.unwrap();
-                        } else {
-                            // We have a number after no known atom, this is an error.
-                            return Err(Box::new(ToEmpiricalFormulaError::NumberAfterUnknowAtom));
+                }
+
+                // Letters with the same case as the input.
+                if is_mixed_case {
+                    for (recased_char, input_char) in new_candidate[candidate.len()..]
+                        .chars()
+                        .zip(letters.chars())
+                    {
+                        if recased_char == input_char {
+                            score += 5;
                         }
                     }
-                    _ => (),
                 }
 
-                // Updating the cursor.
-                cursor_index += count_string.len();
-            }
-            _ => {
-                debug!("leaving char: {current_char}");
-                cursor_index += 1;
+                new_candidates.push((new_candidate, score));
             }
         }
 
-        previous_char = Some(current_char);
+        // Keeping the best partial candidates only: the score of a run does not depend on the
+        // other runs, so the best formulas extend the best partial candidates.
+        new_candidates.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        new_candidates.truncate(MAX_FORMULA_SUGGESTIONS * 4);
+        candidates = new_candidates;
+    }
+
+    let mut suggestions: Vec<FormulaSuggestion> = Vec::new();
+    for (candidate, score) in candidates {
+        if suggestions.iter().any(|s| s.formula == candidate) {
+            continue;
+        }
+        if let Ok(empirical_formula) = to_empirical_formula(&candidate) {
+            suggestions.push(FormulaSuggestion {
+                formula: candidate,
+                empirical_formula,
+                score,
+            });
+        }
     }
 
-    debug!("{atom_blocks:#?}");
+    suggestions.sort_by(|a, b| b.score.cmp(&a.score).then(a.formula.cmp(&b.formula)));
+    suggestions.truncate(MAX_FORMULA_SUGGESTIONS);
 
-    // Building a map from atom_count.
-    let mut atom_count_map: HashMap<String, usize> = HashMap::new();
+    debug!("suggestions: {suggestions:#?}");
 
-    for atom_block in &atom_blocks {
-        if atom_count_map.contains_key(&atom_block.atom_name) {
-            match atom_count_map.get_mut(&atom_block.atom_name) {
-                Some(atom_count) => *atom_count += atom_block.count,
-                None => {
-                    // Should never happen.
-                    return Err(Box::new(ToEmpiricalFormulaError::UnexpectedNoneAtomCount(
-                        atom_block.atom_name.clone(),
-                    )));
-                }
+    Ok(suggestions)
+}
+
+/// Returns all the ways to split a run of letters into atoms, ignoring the case.
+fn segment_letters(letters: &str) -> Vec<Vec<String>> {
+    fn segment(letters: &[u8], current: &mut Vec<String>, segmentations: &mut Vec<Vec<String>>) {
+        if segmentations.len() >= MAX_SEGMENTATIONS {
+            return;
+        }
+        if letters.is_empty() {
+            segmentations.push(current.clone());
+            return;
+        }
+
+        for atom_length in 1..=2.min(letters.len()) {
+            let mut atom = String::with_capacity(atom_length);
+            atom.push(letters[0].to_ascii_uppercase() as char);
+            if atom_length == 2 {
+                atom.push(letters[1].to_ascii_lowercase() as char);
+            }
+
+            if element_by_symbol(&atom).is_some() {
+                current.push(atom);
+                segment(&letters[atom_length..], current, segmentations);
+                current.pop();
             }
-        } else {
-            atom_count_map.insert(atom_block.atom_name.clone(), atom_block.count);
         }
     }
 
-    debug!("{atom_count_map:#?}");
+    let mut segmentations: Vec<Vec<String>> = Vec::new();
+    segment(letters.as_bytes(), &mut Vec::new(), &mut segmentations);
 
-    // Building empirical formula.
-    // C, H and then in alphabetical order.
-    let mut final_formula: String = String::new();
+    segmentations
+}
+
+/// Common abbreviations of organic groups, left untouched by `clean_formula`.
+const GROUP_ABBREVIATIONS: [&str; 10] =
+    ["Bn", "Boc", "Bu", "Bz", "Cp", "Et", "Me", "Ms", "Ph", "Tf"];
+
+/// Lowercase letters used as variable counts, as in `(C2H4O)n` or `xH2O`.
+pub(crate) const VARIABLE_COUNTS: [&str; 2] = ["n", "x"];
+
+/// The kind of a change made by `clean_formula`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormulaChangeKind {
+    RemovedWhitespace,
+    ConvertedSubscript,
+    ConvertedSuperscript,
+    ConvertedDash,
+    ConvertedBracket,
+    ConvertedSeparator,
+    FixedCase,
+    RemovedCharacter,
+}
+
+/// A change made by `clean_formula`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormulaChange {
+    pub kind: FormulaChangeKind,
+    /// The char index of the change in the input formula.
+    pub position: usize,
+    pub from: String,
+    pub to: String,
+}
 
-    if atom_count_map.contains_key("C") {
-        if atom_count_map.get("C").unwrap() == &1 {
-            final_formula.push('C');
+impl Display for FormulaChange {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if self.to.is_empty() {
+            write!(f, "removed {:?} at {}", self.from, self.position)
         } else {
-            final_formula.push_str(format!("C{}", atom_count_map.get("C").unwrap()).as_str());
+            write!(
+                f,
+                "replaced {:?} by {:?} at {}",
+                self.from, self.to, self.position
+            )
         }
+    }
+}
 
-        atom_count_map.remove("C");
+/// A formula cleaned by `clean_formula` with the list of changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CleanedFormula {
+    pub formula: String,
+    pub changes: Vec<FormulaChange>,
+}
+
+/// Cleans a formula for display, keeping its structure.
+/// Unlike `to_empirical_formula` the linear formula, as `CH3(CH2)4COOH`, is kept:
+/// - whitespaces are removed
+/// - unicode subscripts, superscripts and dashes are converted
+/// - `{}` are converted into `[]` and adduct separators into `·`
+/// - the case of the atoms is fixed when there is a single possible reading: `nacl` -> `NaCl` but `co` is kept
+/// - any other char is removed
+pub fn clean_formula(formula: &str) -> Result<CleanedFormula, Box<dyn Error + Send + Sync>> {
+    // Rejecting empty formulas.
+    if formula.is_empty() {
+        return Err(Box::new(ToEmpiricalFormulaError::EmptyFormula));
     }
-    if atom_count_map.contains_key("H") {
-        if atom_count_map.get("H").unwrap() == &1 {
-            final_formula.push('H');
-        } else {
-            final_formula.push_str(format!("H{}", atom_count_map.get("H").unwrap()).as_str());
+
+    let mut changes: Vec<FormulaChange> = Vec::new();
+    // Kept chars with their position in the input.
+    let mut cleaned_chars: Vec<(usize, char)> = Vec::new();
+
+    for (position, current_char) in formula.chars().enumerate() {
+        let (kind, maybe_new_char) = match current_char {
+            'A'..='Z'
+            | 'a'..='z'
+            | '0'..='9'
+            | '('
+            | ')'
+            | '['
+            | ']'
+            | '+'
+            | '-'
+            | '='
+            | '#'
+            | '≡'
+            | '·'
+            | '.'
+            | ','
+            | '/'
+            | '^' => {
+                cleaned_chars.push((position, current_char));
+                continue;
+            }
+            '₀'..='₉' => (
+                FormulaChangeKind::ConvertedSubscript,
+                char::from_digit(current_char as u32 - '₀' as u32, 10),
+            ),
+            '⁰' => (FormulaChangeKind::ConvertedSuperscript, Some('0')),
+            '¹' => (FormulaChangeKind::ConvertedSuperscript, Some('1')),
+            '²' => (FormulaChangeKind::ConvertedSuperscript, Some('2')),
+            '³' => (FormulaChangeKind::ConvertedSuperscript, Some('3')),
+            '⁴'..='⁹' => (
+                FormulaChangeKind::ConvertedSuperscript,
+                char::from_digit(current_char as u32 - '⁰' as u32, 10),
+            ),
+            '⁺' => (FormulaChangeKind::ConvertedSuperscript, Some('+')),
+            '⁻' => (FormulaChangeKind::ConvertedSuperscript, Some('-')),
+            '‐' | '‑' | '‒' | '–' | '—' | '−' => {
+                (FormulaChangeKind::ConvertedDash, Some('-'))
+            }
+            '{' => (FormulaChangeKind::ConvertedBracket, Some('[')),
+            '}' => (FormulaChangeKind::ConvertedBracket, Some(']')),
+            '•' | '∙' | '⋅' | '*' => (FormulaChangeKind::ConvertedSeparator, Some('·')),
+            c if c.is_whitespace() => (FormulaChangeKind::RemovedWhitespace, None),
+            _ => (FormulaChangeKind::RemovedCharacter, None),
+        };
+
+        if let Some(new_char) = maybe_new_char {
+            cleaned_chars.push((position, new_char));
         }
-        atom_count_map.remove("H");
+
+        changes.push(FormulaChange {
+            kind,
+            position,
+            from: current_char.to_string(),
+            to: maybe_new_char.map(String::from).unwrap_or_default(),
+        });
     }
 
-    // Sorting the last atoms.
-    let mut sorted_keys: Vec<&String> = atom_count_map.keys().collect();
-    sorted_keys.sort();
+    // Fixing the case of the runs of letters.
+    let mut cleaned_formula = String::with_capacity(formula.len());
+    let mut index = 0;
+    while index < cleaned_chars.len() {
+        let (position, current_char) = cleaned_chars[index];
 
-    for key in sorted_keys {
-        let (atom_name, atom_count) = atom_count_map.get_key_value(key).unwrap();
+        if !current_char.is_ascii_alphabetic() {
+            cleaned_formula.push(current_char);
+            index += 1;
+            continue;
+        }
 
-        if atom_count == &1 {
-            final_formula.push_str(atom_name.clone().as_str());
+        let run_length = cleaned_chars[index..]
+            .iter()
+            .take_while(|(_, c)| c.is_ascii_alphabetic())
+            .count();
+        let letters: String = cleaned_chars[index..index + run_length]
+            .iter()
+            .map(|(_, c)| c)
+            .collect();
+        index += run_length;
+
+        let segmentations = segment_letters(&letters);
+        let is_valid_as_typed = segmentations
+            .iter()
+            .any(|segmentation| segmentation.concat() == letters);
+        let is_untouchable = VARIABLE_COUNTS.contains(&letters.as_str())
+            || GROUP_ABBREVIATIONS
+                .iter()
+                .any(|abbreviation| letters.contains(abbreviation));
+
+        if !is_valid_as_typed && !is_untouchable && segmentations.len() == 1 {
+            let fixed_letters = segmentations[0].concat();
+            debug!("fixed case: {letters} -> {fixed_letters}");
+
+            cleaned_formula.push_str(&fixed_letters);
+            changes.push(FormulaChange {
+                kind: FormulaChangeKind::FixedCase,
+                position,
+                from: letters,
+                to: fixed_letters,
+            });
         } else {
-            final_formula.push_str(format!("{atom_name}{atom_count}").as_str());
+            cleaned_formula.push_str(&letters);
         }
     }
 
-    debug!("final_formula: {final_formula}");
+    changes.sort_by_key(|change| change.position);
+
+    debug!("cleaned_formula: {cleaned_formula} changes: {changes:#?}");
 
-    Ok(final_formula)
+    Ok(CleanedFormula {
+        formula: cleaned_formula,
+        changes,
+    })
 }
 
 #[cfg(test)]
@@ -427,6 +427,358 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_formula_parser() {
+        init_logger();
+
+        // Sibling bracket groups and large counts.
+        let formulas_empirical_formulas = vec![
+            ("HOC(COOH)(CH2COOH)2", "C6H8O7"),
+            ("(CH3)2NCH2CH2N(CH3)2", "C6H16N2"),
+            ("C6H5CH2P(O)(OC2H5)2", "C11H17O3P"),
+            ("CH3(CH2)11N(CH3)3Br", "C15H34BrN"),
+            ("C100H202", "C100H202"),
+            ("[Fe(CN)6]4-", "C6FeN6"),
+            ("2-(HO)C6H4CHO", "C7H6O2"),
+            ("CuSO4·5H2O", "H10CuO9S"),
+            ("CaSO4·0.5H2O", "H2Ca2O9S2"),
+            // Hydrate water is counted, an unknown hydration is dropped.
+            ("C15H10O7 · xH2O", "C15H10O7"),
+            ("C15H10O7·nH2O", "C15H10O7"),
+            ("Na2CO3·10H2O", "CH20Na2O13"),
+            // The leading multiplier of an adduct multiplies its first part only.
+            ("2CaSO4·H2O", "H2Ca2O9S2"),
+            ("2NaCl", "ClNa"),
+        ];
+
+        let mut parser = FormulaParser::new();
+        for (formula, empirical_formula) in formulas_empirical_formulas {
+            assert_eq!(
+                parser.to_empirical_formula(formula).unwrap(),
+                empirical_formula,
+                "{formula}"
+            );
+        }
+
+        let atom_counts = parser.parse("CDCl3").unwrap();
+        let carbon = crate::element::element_by_symbol("C").unwrap();
+        let deuterium = crate::element::element_by_symbol("D").unwrap();
+        assert_eq!(atom_counts.get(carbon), 1);
+        assert_eq!(atom_counts.get(deuterium), 1);
+        assert!((atom_counts.molar_mass() - 120.375).abs() < 0.001);
+        let symbols: Vec<&str> = atom_counts
+            .iter()
+            .map(|(element, _)| element.symbol)
+            .collect();
+        assert_eq!(symbols, vec!["C", "Cl", "D"]);
+        assert!(AtomCounts::default().is_empty());
+
+        let atom_counts = parser.parse("CaSO4·0.5H2O").unwrap();
+        assert_eq!(atom_counts.formula_units(), 2);
+        assert!((atom_counts.molar_mass() - 145.142).abs() < 0.001);
+
+        assert_eq!(
+            parser.parse("Ca(OH2").unwrap_err().to_string(),
+            "unbalanced parenthesis"
+        );
+        assert_eq!(
+            parser
+                .parse("C999999999999999999999999")
+                .unwrap_err()
+                .to_string(),
+            "can not parse number: number too large to fit in target type"
+        );
+        assert_eq!(
+            parser.parse("CxH4").unwrap_err().to_string(),
+            "unknown atom Cx"
+        );
+
+        // Counts overflowing usize.
+        for formula in [
+            "NaCl·9999999999999999999H2O·0.5H2O",
+            "NaCl·0.00000000000000000001H2O·0.5H2O",
+            "NaCl·0.000000000000000000001H2O",
+            "(C9999999999)9999999999",
+        ] {
+            assert_eq!(
+                parser.parse(formula).unwrap_err().to_string(),
+                "atom count overflow",
+                "{formula}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_deuterium() {
+        init_logger();
+
+        assert_eq!(to_empirical_formula("CDCl3").unwrap(), "CCl3D");
+        assert_eq!(to_empirical_formula("D2O").unwrap(), "D2O");
+        assert_eq!(to_empirical_formula("CD3CD2OD").unwrap(), "C2D6O");
+        assert_eq!(to_empirical_formula("CH2D2").unwrap(), "CH2D2");
+        assert!((molar_mass("D2O").unwrap() - 20.028).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_superheavy_elements() {
+        init_logger();
+
+        // All the 118 elements are known, including those named in 2016.
+        for symbol in ["Nh", "Fl", "Mc", "Lv", "Ts", "Og"] {
+            assert_eq!(to_empirical_formula(symbol).unwrap(), symbol);
+        }
+        assert_eq!(to_empirical_formula("OgF4").unwrap(), "F4Og");
+
+        // Temporary systematic symbols are not.
+        assert_eq!(
+            to_empirical_formula("Uuo").unwrap_err().to_string(),
+            "unknown atom Uu"
+        );
+    }
+
+    #[test]
+    fn test_to_empirical_formulas() {
+        init_logger();
+
+        let empirical_formulas = to_empirical_formulas(["CH3CH2OH", "Xy", "", "NaCl"]);
+        assert_eq!(empirical_formulas.len(), 4);
+        assert_eq!(empirical_formulas[0].as_ref().unwrap(), "C2H6O");
+        assert!(empirical_formulas[1].is_err());
+        assert!(empirical_formulas[2].is_err());
+        assert_eq!(empirical_formulas[3].as_ref().unwrap(), "ClNa");
+    }
+
+    #[test]
+    fn test_recover_formula_case() {
+        init_logger();
+
+        let suggestions = recover_formula_case("nacl").unwrap();
+        assert_eq!(suggestions[0].formula, "NaCl");
+        assert_eq!(suggestions[0].empirical_formula, "ClNa");
+
+        let suggestions = recover_formula_case("NACL").unwrap();
+        assert_eq!(suggestions[0].formula, "NaCl");
+
+        // Ambiguous input, both readings are returned.
+        let suggestions = recover_formula_case("co").unwrap();
+        let formulas: Vec<&str> = suggestions.iter().map(|s| s.formula.as_str()).collect();
+        assert_eq!(formulas.len(), 2);
+        assert!(formulas.contains(&"CO"));
+        assert!(formulas.contains(&"Co"));
+
+        // The input case is favoured.
+        let suggestions = recover_formula_case("CO").unwrap();
+        assert_eq!(suggestions[0].formula, "CO");
+        assert_eq!(suggestions[1].formula, "Co");
+        assert!(suggestions[0].score > suggestions[1].score);
+
+        let suggestions = recover_formula_case("Co").unwrap();
+        assert_eq!(suggestions[0].formula, "Co");
+
+        // Numbers and other chars are kept.
+        let suggestions = recover_formula_case("ch3(ch2)4cooh").unwrap();
+        assert_eq!(suggestions[0].formula, "CH3(CH2)4COOH");
+        assert_eq!(suggestions[0].empirical_formula, "C6H12O2");
+
+        let suggestions = recover_formula_case("cuso4").unwrap();
+        assert!(suggestions.iter().any(|s| s.formula == "CuSO4"));
+
+        // The best reading of a long input is kept, whatever the order of the candidates.
+        let formula = "Co2".repeat(10);
+        let suggestions = recover_formula_case(&formula).unwrap();
+        assert_eq!(suggestions[0].formula, formula);
+        assert_eq!(suggestions.len(), 64);
+
+        // No valid segmentation.
+        assert!(recover_formula_case("qq").unwrap().is_empty());
+
+        assert!(recover_formula_case("").is_err());
+    }
+
+    #[test]
+    fn test_molar_mass() {
+        init_logger();
+
+        assert!((molar_mass("H2O").unwrap() - 18.015).abs() < 0.001);
+        assert!((molar_mass("CH3CH2OH").unwrap() - 46.069).abs() < 0.001);
+        assert!((molar_mass("NaCl").unwrap() - 58.44).abs() < 0.001);
+        assert!((molar_mass("CDCl3").unwrap() - 120.375).abs() < 0.001);
+        assert!(molar_mass("").is_err());
+        assert!(molar_mass("Xy").is_err());
+
+        let atom_count_map = to_atom_counts("CH3CH2OH").unwrap().to_hash_map();
+        assert_eq!(atom_count_map.get("C"), Some(&2));
+        assert_eq!(atom_count_map.get("H"), Some(&6));
+        assert_eq!(atom_count_map.get("O"), Some(&1));
+        assert_eq!(empirical_formula_from_atom_counts(&atom_count_map), "C2H6O");
+    }
+
+    #[test]
+    fn test_monoisotopic_mass() {
+        init_logger();
+
+        assert!((monoisotopic_mass("H2O").unwrap() - 18.010_565).abs() < 0.000_001);
+        assert!((monoisotopic_mass("C8H10N4O2").unwrap() - 194.080_376).abs() < 0.000_001);
+        assert!((monoisotopic_mass("CDCl3").unwrap() - 118.920_660).abs() < 0.000_001);
+        assert!((monoisotopic_mass("NH4+").unwrap() - 18.034_374).abs() < 0.000_001);
+        assert!(monoisotopic_mass("Xy").is_err());
+    }
+
+    #[test]
+    fn test_split_formula_charge() {
+        assert_eq!(split_formula_charge("C2H6O"), ("C2H6O", 0));
+        assert_eq!(split_formula_charge("NH4+"), ("NH4", 1));
+        assert_eq!(split_formula_charge("Cl-"), ("Cl", -1));
+        assert_eq!(split_formula_charge("SO4--"), ("SO4", -2));
+        assert_eq!(split_formula_charge("SO4^2-"), ("SO4", -2));
+        assert_eq!(split_formula_charge("SO4 2-"), ("SO4", -2));
+        assert_eq!(split_formula_charge("SO4(2-)"), ("SO4", -2));
+        assert_eq!(split_formula_charge("Fe^3+"), ("Fe", 3));
+        assert_eq!(split_formula_charge("Fe3+"), ("Fe", 3));
+        assert_eq!(split_formula_charge("Cu2+"), ("Cu", 2));
+        assert_eq!(split_formula_charge("O2-"), ("O", -2));
+        assert_eq!(split_formula_charge("OH-"), ("OH", -1));
+        assert_eq!(split_formula_charge("[Fe(CN)6]4-"), ("[Fe(CN)6]", -4));
+        assert_eq!(split_formula_charge("SO42-"), ("SO42", -1));
+        assert_eq!(
+            split_formula_charge("C6H4-1,2-(CO2C2H5)2"),
+            ("C6H4-1,2-(CO2C2H5)2", 0)
+        );
+    }
+
+    #[test]
+    fn test_canonical_key() {
+        init_logger();
+
+        let options = CanonicalKeyOptions::default();
+
+        for formula in ["C2H5OH", "CH3CH2OH", "C2H6O", "OC2H6", "CH3OCH3"] {
+            assert_eq!(to_canonical_key(formula, options).unwrap(), "C2H6O");
+        }
+
+        // Isotopes, only deuterium is read.
+        let keep_deuterium = CanonicalKeyOptions {
+            keep_deuterium: true,
+            ..options
+        };
+        assert_eq!(to_canonical_key("CDCl3", options).unwrap(), "CHCl3");
+        assert_eq!(to_canonical_key("CDCl3", keep_deuterium).unwrap(), "CCl3D");
+        assert_eq!(to_canonical_key("13CH4", keep_deuterium).unwrap(), "CH4");
+        assert_eq!(to_canonical_key("[13C]H4", keep_deuterium).unwrap(), "CH4");
+
+        // Adducts, for their smallest whole number of formula units.
+        for formula in ["CaSO4·0.5H2O", "2CaSO4·H2O", "4CaSO4·2H2O"] {
+            assert_eq!(
+                to_canonical_key(formula, options).unwrap(),
+                "H2Ca2O9S2",
+                "{formula}"
+            );
+        }
+        assert_eq!(
+            to_canonical_key("CuSO4·5H2O", options).unwrap(),
+            to_canonical_key("2CuSO4·10H2O", options).unwrap()
+        );
+        assert_eq!(to_canonical_key("C2H4O2", options).unwrap(), "C2H4O2");
+
+        // Charge.
+        let keep_charge = CanonicalKeyOptions {
+            keep_charge: true,
+            ..options
+        };
+        assert_eq!(to_canonical_key("NH4+", options).unwrap(), "H4N");
+        assert_eq!(to_canonical_key("NH4+", keep_charge).unwrap(), "H4N^+");
+        assert_eq!(to_canonical_key("SO4 2-", keep_charge).unwrap(), "O4S^2-");
+        assert_eq!(to_canonical_key("NH3", keep_charge).unwrap(), "H3N");
+
+        assert!(to_canonical_key("", options).is_err());
+        assert!(to_canonical_key("+", options).is_err());
+        assert!(to_canonical_key("Xy", options).is_err());
+    }
+
+    #[test]
+    fn test_canonical_hash() {
+        let options = CanonicalKeyOptions::default();
+
+        assert_eq!(
+            to_canonical_hash("C2H5OH", options).unwrap(),
+            to_canonical_hash("OC2H6", options).unwrap()
+        );
+        assert_ne!(
+            to_canonical_hash("C2H5OH", options).unwrap(),
+            to_canonical_hash("C2H4O", options).unwrap()
+        );
+        // The hash must stay stable as it is stored.
+        assert_eq!(
+            to_canonical_hash("C2H6O", options).unwrap(),
+            0x4bf4_fd19_0e0c_227f
+        );
+    }
+
+    #[test]
+    fn test_clean_formula() {
+        init_logger();
+
+        // Nothing to clean.
+        let cleaned = clean_formula("CH3(CH2)4COOH").unwrap();
+        assert_eq!(cleaned.formula, "CH3(CH2)4COOH");
+        assert!(cleaned.changes.is_empty());
+
+        let cleaned = clean_formula(" CH₃(CH₂)₄COOH ").unwrap();
+        assert_eq!(cleaned.formula, "CH3(CH2)4COOH");
+        assert_eq!(cleaned.changes.len(), 5);
+        assert_eq!(
+            cleaned.changes[1],
+            FormulaChange {
+                kind: FormulaChangeKind::ConvertedSubscript,
+                position: 3,
+                from: "₃".to_string(),
+                to: "3".to_string(),
+            }
+        );
+        assert_eq!(cleaned.changes[0].to_string(), "removed \" \" at 0");
+        assert_eq!(
+            cleaned.changes[1].to_string(),
+            "replaced \"₃\" by \"3\" at 3"
+        );
+
+        let cleaned = clean_formula("SO₄²⁻").unwrap();
+        assert_eq!(cleaned.formula, "SO42-");
+
+        let cleaned = clean_formula("C6H4–1,2–(CO2CH3)2").unwrap();
+        assert_eq!(cleaned.formula, "C6H4-1,2-(CO2CH3)2");
+        assert_eq!(cleaned.changes[0].kind, FormulaChangeKind::ConvertedDash);
+
+        let cleaned = clean_formula("{Cu(NH3)4}SO4 • H2O").unwrap();
+        assert_eq!(cleaned.formula, "[Cu(NH3)4]SO4·H2O");
+
+        let cleaned = clean_formula("NaCl!?").unwrap();
+        assert_eq!(cleaned.formula, "NaCl");
+        assert_eq!(cleaned.changes[0].kind, FormulaChangeKind::RemovedCharacter);
+
+        // Case fixing.
+        let cleaned = clean_formula("nacl").unwrap();
+        assert_eq!(cleaned.formula, "NaCl");
+        assert_eq!(
+            cleaned.changes,
+            vec![FormulaChange {
+                kind: FormulaChangeKind::FixedCase,
+                position: 0,
+                from: "nacl".to_string(),
+                to: "NaCl".to_string(),
+            }]
+        );
+        assert_eq!(clean_formula("ch3ch2oh").unwrap().formula, "CH3CH2OH");
+        assert_eq!(clean_formula("NaCL").unwrap().formula, "NaCl");
+
+        // Ambiguous or untouchable letters are kept.
+        assert_eq!(clean_formula("co").unwrap().formula, "co");
+        assert_eq!(clean_formula("CO").unwrap().formula, "CO");
+        assert_eq!(clean_formula("(C2H4O)n").unwrap().formula, "(C2H4O)n");
+        assert_eq!(clean_formula("PPh3").unwrap().formula, "PPh3");
+
+        assert!(clean_formula("").is_err());
+    }
+
     #[test]
     fn test_format_unbalanced_parenthesis_error() {
         let error = ToEmpiricalFormulaError::UnbalancedParenthesis;
@@ -468,4 +820,10 @@ mod tests {
         let error = ToEmpiricalFormulaError::EmptyFormula;
         assert_eq!(error.to_string(), "empty formula");
     }
+
+    #[test]
+    fn test_format_count_overflow_error() {
+        let error = ToEmpiricalFormulaError::CountOverflow;
+        assert_eq!(error.to_string(), "atom count overflow");
+    }
 }
@@ -0,0 +1,261 @@
+use log::debug;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+use crate::{
+    formula::{
+        CanonicalKeyOptions, ToEmpiricalFormulaError, VARIABLE_COUNTS, molar_mass, to_canonical_key,
+    },
+    lexer::{TokenKind, tokenize},
+    stoichiometry::Quantity,
+};
+
+/// The formula of water, as returned by `to_canonical_key`.
+const WATER: &str = "H2O";
+
+#[derive(Debug, PartialEq)]
+pub enum HydrateError {
+    InvalidCoefficient(String),
+    CoreMismatch(String, String),
+    InvalidWaterCount(f64),
+    InvalidQuantity,
+}
+
+impl Display for HydrateError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            HydrateError::InvalidCoefficient(s) => write!(f, "invalid coefficient for {s}"),
+            HydrateError::CoreMismatch(from, to) => {
+                write!(f, "{from} and {to} do not have the same core")
+            }
+            HydrateError::InvalidWaterCount(count) => write!(f, "invalid water count {count}"),
+            HydrateError::InvalidQuantity => write!(f, "invalid quantity"),
+        }
+    }
+}
+
+impl std::error::Error for HydrateError {}
+
+/// A solvate molecule with its equivalents per formula unit of the core.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solvate {
+    pub formula: String,
+    pub coefficient: f64,
+}
+
+/// A formula split into its core and its solvates, as `CuSO4` and `5H2O` in `CuSO4·5H2O`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolvatedFormula {
+    pub core: String,
+    pub solvates: Vec<Solvate>,
+}
+
+impl SolvatedFormula {
+    /// Returns the molar mass in g/mol of one formula unit of the core with its solvates.
+    pub fn molar_mass(&self) -> Result<f64, Box<dyn Error + Send + Sync>> {
+        let mut total = molar_mass(&self.core)?;
+
+        for solvate in &self.solvates {
+            total += solvate.coefficient * molar_mass(&solvate.formula)?;
+        }
+
+        Ok(total)
+    }
+
+    /// Returns the number of water molecules per formula unit of the core.
+    #[must_use]
+    pub fn water_count(&self) -> f64 {
+        self.solvates
+            .iter()
+            .filter(|solvate| is_water(&solvate.formula))
+            .map(|solvate| solvate.coefficient)
+            .sum()
+    }
+}
+
+impl Display for SolvatedFormula {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.core)?;
+
+        for solvate in &self.solvates {
+            if (solvate.coefficient - 1.0).abs() < f64::EPSILON {
+                write!(f, "·{}", solvate.formula)?;
+            } else {
+                write!(f, "·{}{}", solvate.coefficient, solvate.formula)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The amounts of a formula converted into another hydration state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HydrateConversion {
+    /// The amount in mol, the same for both hydration states.
+    pub moles: f64,
+    /// The mass in g of the target hydration state.
+    pub mass: f64,
+}
+
+/// Splits a formula into its core and its solvates.
+/// Accepted separators are the `ADDUCT_SEPARATORS` of the lexer, and coefficients may be decimal:
+/// `CaSO4·0.5H2O`. A leading coefficient of the core divides the solvate coefficients:
+/// `2CaSO4·H2O` is `CaSO4·0.5H2O`. A variable coefficient, as in `xH2O`, is an error.
+pub fn split_solvates(formula: &str) -> Result<SolvatedFormula, Box<dyn Error + Send + Sync>> {
+    // Parts as (coefficient, formula).
+    let mut parts: Vec<(f64, &str)> = Vec::new();
+    let mut part_start = 0;
+    let mut coefficient = 1.0;
+    let mut expect_coefficient = true;
+
+    let mut tokens = tokenize(formula).peekable();
+    while let Some(token) = tokens.next() {
+        match token.kind {
+            TokenKind::AdductSeparator => {
+                parts.push((coefficient, &formula[part_start..token.span.start]));
+                part_start = token.span.end;
+                coefficient = 1.0;
+                expect_coefficient = true;
+            }
+            TokenKind::Whitespace => (),
+            TokenKind::Unknown if expect_coefficient && VARIABLE_COUNTS.contains(&token.text) => {
+                return Err(Box::new(HydrateError::InvalidCoefficient(
+                    formula[part_start..].trim().to_string(),
+                )));
+            }
+            TokenKind::Multiplier(_) | TokenKind::FractionalMultiplier(_) if expect_coefficient => {
+                // A locant, as in 2-(HO)C6H4CHO, is not a coefficient.
+                let is_coefficient = tokens.peek().is_some_and(|next_token| {
+                    matches!(
+                        next_token.kind,
+                        TokenKind::Element(_) | TokenKind::OpenBracket(_) | TokenKind::Whitespace
+                    )
+                });
+
+                if is_coefficient {
+                    coefficient = token.text.parse::<f64>().unwrap_or(1.0);
+                    part_start = token.span.end;
+                }
+                expect_coefficient = false;
+            }
+            _ => expect_coefficient = false,
+        }
+    }
+    parts.push((coefficient, &formula[part_start..]));
+
+    debug!("parts: {parts:?}");
+
+    let mut parts = parts.into_iter();
+    let Some((core_coefficient, core)) = parts.next() else {
+        return Err(Box::new(ToEmpiricalFormulaError::EmptyFormula));
+    };
+
+    let core = core.trim();
+    if core.is_empty() {
+        return Err(Box::new(ToEmpiricalFormulaError::EmptyFormula));
+    }
+    if core_coefficient <= 0.0 {
+        return Err(Box::new(HydrateError::InvalidCoefficient(core.to_string())));
+    }
+
+    let mut solvates: Vec<Solvate> = Vec::new();
+
+    for (coefficient, solvate) in parts {
+        let solvate = solvate.trim();
+
+        if solvate.is_empty() {
+            return Err(Box::new(ToEmpiricalFormulaError::EmptyFormula));
+        }
+        if coefficient <= 0.0 {
+            return Err(Box::new(HydrateError::InvalidCoefficient(
+                solvate.to_string(),
+            )));
+        }
+
+        solvates.push(Solvate {
+            formula: solvate.to_string(),
+            coefficient: coefficient / core_coefficient,
+        });
+    }
+
+    Ok(SolvatedFormula {
+        core: core.to_string(),
+        solvates,
+    })
+}
+
+/// Returns the formula with the given number of water molecules, other solvates are kept.
+/// `("CuSO4·5H2O", 0.0)` gives `CuSO4`, `("CuSO4", 5.0)` gives `CuSO4·5H2O`.
+pub fn with_water_count(
+    formula: &str,
+    water_count: f64,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    if !water_count.is_finite() || water_count < 0.0 {
+        return Err(Box::new(HydrateError::InvalidWaterCount(water_count)));
+    }
+
+    let mut solvated_formula = split_solvates(formula)?;
+
+    solvated_formula
+        .solvates
+        .retain(|solvate| !is_water(&solvate.formula));
+
+    if water_count > 0.0 {
+        solvated_formula.solvates.push(Solvate {
+            formula: WATER.to_string(),
+            coefficient: water_count,
+        });
+    }
+
+    Ok(solvated_formula.to_string())
+}
+
+/// Converts a quantity of a formula into the equivalent quantity of another hydration state
+/// of the same core: 10 g of `CuSO4` are 15.64 g of `CuSO4·5H2O`.
+pub fn convert_quantity(
+    quantity: Quantity,
+    from: &str,
+    to: &str,
+) -> Result<HydrateConversion, Box<dyn Error + Send + Sync>> {
+    let from_formula = split_solvates(from)?;
+    let to_formula = split_solvates(to)?;
+
+    let options = CanonicalKeyOptions::default();
+    if to_canonical_key(&from_formula.core, options)?
+        != to_canonical_key(&to_formula.core, options)?
+    {
+        return Err(Box::new(HydrateError::CoreMismatch(
+            from.to_string(),
+            to.to_string(),
+        )));
+    }
+
+    let moles = quantity.to_moles(from_formula.molar_mass()?);
+    if !moles.is_finite() || moles < 0.0 {
+        return Err(Box::new(HydrateError::InvalidQuantity));
+    }
+
+    let mass = moles * to_formula.molar_mass()?;
+
+    debug!("moles: {moles} mass: {mass}");
+
+    Ok(HydrateConversion { moles, mass })
+}
+
+/// Converts a mass in g of a formula into the equivalent mass of another hydration state.
+/// See `convert_quantity`.
+pub fn convert_mass(mass: f64, from: &str, to: &str) -> Result<f64, Box<dyn Error + Send + Sync>> {
+    Ok(convert_quantity(Quantity::Mass(mass), from, to)?.mass)
+}
+
+/// Returns true if the formula is water.
+fn is_water(formula: &str) -> bool {
+    to_canonical_key(formula, CanonicalKeyOptions::default()).is_ok_and(|key| key == WATER)
+}
+
+#[cfg(test)]
+#[path = "hydrate_tests.rs"]
+mod hydrate_tests;
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::{hydrate::*, stoichiometry::Quantity, test_helpers::assert_close};
+
+    fn init_logger() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    /// Tolerance of `assert_close`.
+    const TOLERANCE: f64 = 0.001;
+
+    #[test]
+    fn test_split_solvates() {
+        init_logger();
+
+        let solvated_formula = split_solvates("CuSO4·5H2O").unwrap();
+        assert_eq!(solvated_formula.core, "CuSO4");
+        assert_eq!(
+            solvated_formula.solvates,
+            vec![Solvate {
+                formula: "H2O".to_string(),
+                coefficient: 5.0,
+            }]
+        );
+        assert_close(5.0, solvated_formula.water_count(), TOLERANCE);
+        assert_close(249.677, solvated_formula.molar_mass().unwrap(), TOLERANCE);
+
+        let solvated_formula = split_solvates("CaSO4 · 0.5 H2O").unwrap();
+        assert_eq!(solvated_formula.core, "CaSO4");
+        assert_close(0.5, solvated_formula.water_count(), TOLERANCE);
+        assert_eq!(solvated_formula.to_string(), "CaSO4·0.5H2O");
+
+        let solvated_formula = split_solvates("2CaSO4.H2O").unwrap();
+        assert_eq!(solvated_formula.core, "CaSO4");
+        assert_close(0.5, solvated_formula.water_count(), TOLERANCE);
+
+        let solvated_formula = split_solvates("C5H5N·HCl").unwrap();
+        assert_eq!(solvated_formula.solvates[0].formula, "HCl");
+        assert_close(0.0, solvated_formula.water_count(), TOLERANCE);
+
+        // A locant is not a coefficient.
+        let solvated_formula = split_solvates("2-(HO)C6H4CHO·H2O").unwrap();
+        assert_eq!(solvated_formula.core, "2-(HO)C6H4CHO");
+
+        assert_eq!(split_solvates("NaCl").unwrap().solvates, vec![]);
+        assert!(split_solvates("").is_err());
+        assert!(split_solvates("CuSO4·").is_err());
+        assert!(split_solvates("CuSO4·0H2O").is_err());
+        assert_eq!(
+            split_solvates("C15H10O7 · xH2O").unwrap_err().to_string(),
+            "invalid coefficient for xH2O"
+        );
+    }
+
+    #[test]
+    fn test_with_water_count() {
+        init_logger();
+
+        assert_eq!(with_water_count("CuSO4·5H2O", 0.0).unwrap(), "CuSO4");
+        assert_eq!(with_water_count("CuSO4", 5.0).unwrap(), "CuSO4·5H2O");
+        assert_eq!(with_water_count("CuSO4·3H2O", 1.0).unwrap(), "CuSO4·H2O");
+        assert_eq!(
+            with_water_count("C5H5N·HCl", 2.0).unwrap(),
+            "C5H5N·HCl·2H2O"
+        );
+        assert!(with_water_count("CuSO4", -1.0).is_err());
+    }
+
+    #[test]
+    fn test_convert_quantity() {
+        init_logger();
+
+        assert_close(
+            15.644,
+            convert_mass(10.0, "CuSO4", "CuSO4·5H2O").unwrap(),
+            TOLERANCE,
+        );
+        assert_close(
+            10.0,
+            convert_mass(15.643_726, "CuSO4·5H2O", "CuSO4").unwrap(),
+            TOLERANCE,
+        );
+        assert_close(
+            9.379,
+            convert_mass(10.0, "CaSO4·0.5H2O", "CaSO4").unwrap(),
+            TOLERANCE,
+        );
+
+        let conversion = convert_quantity(Quantity::Moles(0.1), "CuSO4·5H2O", "CuSO4·H2O").unwrap();
+        assert_close(0.1, conversion.moles, TOLERANCE);
+        assert_close(17.762, conversion.mass, TOLERANCE);
+
+        assert_eq!(
+            convert_mass(10.0, "CuSO4", "FeSO4·7H2O")
+                .unwrap_err()
+                .to_string(),
+            "CuSO4 and FeSO4·7H2O do not have the same core"
+        );
+        assert!(convert_mass(-1.0, "CuSO4", "CuSO4·5H2O").is_err());
+    }
+}
@@ -0,0 +1,216 @@
+use log::debug;
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::{
+    checksum::weighted_digit_sum,
+    define::{ALL_ZERO_RE, INDEX_NUMBER_RE},
+    element::{Element, element_by_atomic_number},
+};
+
+/// Organic substance classes of the first group of index numbers, CLP Annex VI table 1.1.
+const ORGANIC_CLASSES: [(u16, &str); 20] = [
+    (601, "hydrocarbons"),
+    (602, "halogenated hydrocarbons"),
+    (603, "alcohols and their derivatives"),
+    (604, "phenols and their derivatives"),
+    (605, "aldehydes and their derivatives"),
+    (606, "ketones and their derivatives"),
+    (607, "organic acids and their derivatives"),
+    (608, "nitriles"),
+    (609, "nitro compounds"),
+    (610, "chloronitro and bromonitro compounds"),
+    (611, "azoxy and azo compounds"),
+    (612, "amine compounds"),
+    (613, "heterocyclic bases and their derivatives"),
+    (614, "glycosides and alkaloids"),
+    (615, "cyanates and isocyanates"),
+    (616, "amides and their derivatives"),
+    (617, "organic peroxides"),
+    (647, "enzymes"),
+    (648, "complex coal derived substances"),
+    (649, "complex oil derived substances"),
+];
+
+/// The first group of the substances that fit no other group.
+const MISCELLANEOUS_GROUP: u16 = 650;
+
+#[derive(Debug, PartialEq)]
+pub enum IndexNumberError {
+    EmptyIndexNumber,
+    DigitGroupsCapture,
+    CharTodigitConversion(char),
+    CheckDigitDoesNotMatch,
+    CheckDigitIsTen,
+    AllZeros,
+}
+
+impl Display for IndexNumberError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match &self {
+            IndexNumberError::EmptyIndexNumber => write!(f, "empty index number"),
+            IndexNumberError::DigitGroupsCapture => write!(f, "can not capture digit groups"),
+            IndexNumberError::CharTodigitConversion(char) => {
+                write!(f, "can not convert {char} into digit")
+            }
+            IndexNumberError::CheckDigitDoesNotMatch => write!(f, "check digit does not match"),
+            IndexNumberError::AllZeros => write!(f, "all zeros"),
+            IndexNumberError::CheckDigitIsTen => write!(
+                f,
+                "computed check digit is 10, such numbers are never assigned"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IndexNumberError {}
+
+/// What the first group of an index number refers to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndexNumberGroup {
+    /// The most characteristic element, by atomic number: 082 is lead.
+    Element(&'static Element),
+    /// The most characteristic organic group: 603 is alcohols and their derivatives.
+    OrganicClass(u16, &'static str),
+    /// 650, substances that fit no other group.
+    Miscellaneous,
+}
+
+/// A CLP Annex VI index number that went through `is_index_number`, as `603-002-00-5`.
+/// Ordered by numeric value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IndexNumber {
+    first_group: u16,
+    serial_number: u16,
+    variant: u8,
+    check_digit: u8,
+}
+
+impl IndexNumber {
+    /// Returns the first group, 603 in `603-002-00-5`, see `group`.
+    #[must_use]
+    pub fn first_group(&self) -> u16 {
+        self.first_group
+    }
+
+    /// Returns the serial number of the substance in the group, 2 in `603-002-00-5`.
+    #[must_use]
+    pub fn serial_number(&self) -> u16 {
+        self.serial_number
+    }
+
+    /// Returns the variant of the substance, as an isomer or a form, 0 in `603-002-00-5`.
+    #[must_use]
+    pub fn variant(&self) -> u8 {
+        self.variant
+    }
+
+    /// Returns the check digit, 5 in `603-002-00-5`.
+    #[must_use]
+    pub fn check_digit(&self) -> u8 {
+        self.check_digit
+    }
+
+    /// Decodes the first group into an element or an organic class.
+    /// Returns `None` for the groups not in CLP Annex VI.
+    #[must_use]
+    pub fn group(&self) -> Option<IndexNumberGroup> {
+        if self.first_group == MISCELLANEOUS_GROUP {
+            return Some(IndexNumberGroup::Miscellaneous);
+        }
+
+        if let Some((code, name)) = ORGANIC_CLASSES
+            .iter()
+            .find(|(code, _)| *code == self.first_group)
+        {
+            return Some(IndexNumberGroup::OrganicClass(*code, name));
+        }
+
+        u8::try_from(self.first_group)
+            .ok()
+            .and_then(element_by_atomic_number)
+            .map(IndexNumberGroup::Element)
+    }
+}
+
+impl FromStr for IndexNumber {
+    type Err = Box<dyn std::error::Error + Send + Sync>;
+
+    fn from_str(number: &str) -> Result<Self, Self::Err> {
+        // Rejecting empty numbers.
+        if number.is_empty() {
+            return Err(Box::new(IndexNumberError::EmptyIndexNumber));
+        }
+
+        // Capture groups and check number.
+        let Some(captures) = INDEX_NUMBER_RE.captures(number) else {
+            return Err(Box::new(IndexNumberError::DigitGroupsCapture));
+        };
+
+        let group1 = &captures["group1"];
+        let group2 = &captures["group2"];
+        let group3 = &captures["group3"];
+        let check_digit: u32 = captures["checkdigit"].parse()?;
+        debug!("group1:{group1} - group2:{group2} - group3:{group3} - check_digit:{check_digit}");
+
+        if ALL_ZERO_RE.is_match(group1)
+            && ALL_ZERO_RE.is_match(group2)
+            && ALL_ZERO_RE.is_match(group3)
+        {
+            return Err(Box::new(IndexNumberError::AllZeros));
+        }
+
+        // Digits weighted by their position from the left, modulo 11, as for EC numbers.
+        let modulo = weighted_digit_sum(
+            group1.chars().chain(group2.chars()).chain(group3.chars()),
+            11,
+        )
+        .map_err(IndexNumberError::CharTodigitConversion)?;
+        debug!("modulo:{modulo}");
+
+        if modulo == 10 {
+            return Err(Box::new(IndexNumberError::CheckDigitIsTen));
+        }
+        if check_digit != modulo {
+            return Err(Box::new(IndexNumberError::CheckDigitDoesNotMatch));
+        }
+
+        Ok(IndexNumber {
+            first_group: group1.parse()?,
+            serial_number: group2.parse()?,
+            variant: group3.parse()?,
+            check_digit: u8::try_from(check_digit)?,
+        })
+    }
+}
+
+impl TryFrom<&str> for IndexNumber {
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn try_from(number: &str) -> Result<Self, Self::Error> {
+        number.parse()
+    }
+}
+
+impl Display for IndexNumber {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{:03}-{:03}-{:02}-{}",
+            self.first_group, self.serial_number, self.variant, self.check_digit
+        )
+    }
+}
+
+/// <https://echa.europa.eu/information-on-chemicals/annex-vi-to-clp>
+/// Check if a string is a valid CLP Annex VI index number, as `603-002-00-5`.
+/// See `IndexNumber` for a validated value.
+pub fn is_index_number(number: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    number.parse::<IndexNumber>().map(|_| ())
+}
+
+#[cfg(test)]
+#[path = "indexnumber_tests.rs"]
+mod indexnumber_tests;
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::indexnumber::*;
+
+    fn init_logger() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    #[test]
+    fn test_valid_index_numbers() {
+        init_logger();
+
+        for index_number in [
+            "603-002-00-5",
+            "601-001-00-4",
+            "082-001-00-6",
+            "028-002-00-7",
+            "605-001-00-5",
+            "650-001-00-0",
+        ] {
+            assert!(
+                is_index_number(index_number).is_ok(),
+                "-> error {index_number}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_index_numbers() {
+        init_logger();
+
+        for index_number in [
+            "",
+            "603-002-00",
+            "603-002-0-5",
+            "603-02-00-5",
+            "6030-002-00-5",
+            "603-002-00-55",
+            "603-002-00-A",
+            "603002005",
+            " 603-002-00-5",
+            "603-002-00-6",
+            "000-000-00-0",
+        ] {
+            assert!(
+                is_index_number(index_number).is_err(),
+                "-> error {index_number}"
+            );
+        }
+
+        assert_eq!(
+            is_index_number("").unwrap_err().to_string(),
+            "empty index number"
+        );
+        assert_eq!(
+            is_index_number("603-002-00-6").unwrap_err().to_string(),
+            "check digit does not match"
+        );
+        assert_eq!(
+            is_index_number("000-000-00-0").unwrap_err().to_string(),
+            "all zeros"
+        );
+        assert_eq!(
+            is_index_number("603-001-00-0").unwrap_err().to_string(),
+            "computed check digit is 10, such numbers are never assigned"
+        );
+    }
+
+    #[test]
+    fn test_index_number() {
+        init_logger();
+
+        let index_number: IndexNumber = "603-002-00-5".parse().unwrap();
+        assert_eq!(index_number.first_group(), 603);
+        assert_eq!(index_number.serial_number(), 2);
+        assert_eq!(index_number.variant(), 0);
+        assert_eq!(index_number.check_digit(), 5);
+        assert_eq!(index_number.to_string(), "603-002-00-5");
+        assert_eq!(
+            index_number.group(),
+            Some(IndexNumberGroup::OrganicClass(
+                603,
+                "alcohols and their derivatives"
+            ))
+        );
+
+        let index_number = IndexNumber::try_from("082-001-00-6").unwrap();
+        assert_eq!(index_number.to_string(), "082-001-00-6");
+        let Some(IndexNumberGroup::Element(element)) = index_number.group() else {
+            panic!("no element for {index_number}");
+        };
+        assert_eq!(element.symbol, "Pb");
+
+        assert_eq!(
+            IndexNumber::try_from("650-001-00-0").unwrap().group(),
+            Some(IndexNumberGroup::Miscellaneous)
+        );
+        assert_eq!(IndexNumber::try_from("999-001-00-5").unwrap().group(), None);
+
+        let mut index_numbers: Vec<IndexNumber> = ["603-002-00-5", "082-001-00-6", "601-001-00-4"]
+            .iter()
+            .map(|number| number.parse().unwrap())
+            .collect();
+        index_numbers.sort();
+        assert_eq!(index_numbers[0].to_string(), "082-001-00-6");
+    }
+}
@@ -0,0 +1,354 @@
+/// A common ion.
+#[derive(Debug, PartialEq)]
+pub struct Ion {
+    pub formula: &'static str,
+    pub name: &'static str,
+    pub charge: i8,
+}
+
+/// Common polyatomic ions.
+pub static POLYATOMIC_IONS: [Ion; 42] = [
+    Ion {
+        formula: "NH4",
+        name: "ammonium",
+        charge: 1,
+    },
+    Ion {
+        formula: "H3O",
+        name: "hydronium",
+        charge: 1,
+    },
+    Ion {
+        formula: "OH",
+        name: "hydroxide",
+        charge: -1,
+    },
+    Ion {
+        formula: "CN",
+        name: "cyanide",
+        charge: -1,
+    },
+    Ion {
+        formula: "OCN",
+        name: "cyanate",
+        charge: -1,
+    },
+    Ion {
+        formula: "SCN",
+        name: "thiocyanate",
+        charge: -1,
+    },
+    Ion {
+        formula: "N3",
+        name: "azide",
+        charge: -1,
+    },
+    Ion {
+        formula: "NO3",
+        name: "nitrate",
+        charge: -1,
+    },
+    Ion {
+        formula: "NO2",
+        name: "nitrite",
+        charge: -1,
+    },
+    Ion {
+        formula: "ClO4",
+        name: "perchlorate",
+        charge: -1,
+    },
+    Ion {
+        formula: "ClO3",
+        name: "chlorate",
+        charge: -1,
+    },
+    Ion {
+        formula: "ClO2",
+        name: "chlorite",
+        charge: -1,
+    },
+    Ion {
+        formula: "ClO",
+        name: "hypochlorite",
+        charge: -1,
+    },
+    Ion {
+        formula: "BrO3",
+        name: "bromate",
+        charge: -1,
+    },
+    Ion {
+        formula: "IO3",
+        name: "iodate",
+        charge: -1,
+    },
+    Ion {
+        formula: "IO4",
+        name: "periodate",
+        charge: -1,
+    },
+    Ion {
+        formula: "MnO4",
+        name: "permanganate",
+        charge: -1,
+    },
+    Ion {
+        formula: "HCO3",
+        name: "hydrogen carbonate",
+        charge: -1,
+    },
+    Ion {
+        formula: "HSO4",
+        name: "hydrogen sulfate",
+        charge: -1,
+    },
+    Ion {
+        formula: "HSO3",
+        name: "hydrogen sulfite",
+        charge: -1,
+    },
+    Ion {
+        formula: "HS",
+        name: "hydrogen sulfide",
+        charge: -1,
+    },
+    Ion {
+        formula: "H2PO4",
+        name: "dihydrogen phosphate",
+        charge: -1,
+    },
+    Ion {
+        formula: "CH3COO",
+        name: "acetate",
+        charge: -1,
+    },
+    Ion {
+        formula: "HCOO",
+        name: "formate",
+        charge: -1,
+    },
+    Ion {
+        formula: "BF4",
+        name: "tetrafluoroborate",
+        charge: -1,
+    },
+    Ion {
+        formula: "PF6",
+        name: "hexafluorophosphate",
+        charge: -1,
+    },
+    Ion {
+        formula: "HPO4",
+        name: "hydrogen phosphate",
+        charge: -2,
+    },
+    Ion {
+        formula: "CO3",
+        name: "carbonate",
+        charge: -2,
+    },
+    Ion {
+        formula: "SO4",
+        name: "sulfate",
+        charge: -2,
+    },
+    Ion {
+        formula: "SO3",
+        name: "sulfite",
+        charge: -2,
+    },
+    Ion {
+        formula: "S2O3",
+        name: "thiosulfate",
+        charge: -2,
+    },
+    Ion {
+        formula: "S2O8",
+        name: "peroxodisulfate",
+        charge: -2,
+    },
+    Ion {
+        formula: "CrO4",
+        name: "chromate",
+        charge: -2,
+    },
+    Ion {
+        formula: "Cr2O7",
+        name: "dichromate",
+        charge: -2,
+    },
+    Ion {
+        formula: "MoO4",
+        name: "molybdate",
+        charge: -2,
+    },
+    Ion {
+        formula: "C2O4",
+        name: "oxalate",
+        charge: -2,
+    },
+    Ion {
+        formula: "SiO3",
+        name: "silicate",
+        charge: -2,
+    },
+    Ion {
+        formula: "O2",
+        name: "peroxide",
+        charge: -2,
+    },
+    Ion {
+        formula: "PO4",
+        name: "phosphate",
+        charge: -3,
+    },
+    Ion {
+        formula: "PO3",
+        name: "phosphite",
+        charge: -3,
+    },
+    Ion {
+        formula: "AsO4",
+        name: "arsenate",
+        charge: -3,
+    },
+    Ion {
+        formula: "BO3",
+        name: "borate",
+        charge: -3,
+    },
+];
+
+/// Common monatomic anions.
+pub static MONATOMIC_ANIONS: [Ion; 14] = [
+    Ion {
+        formula: "H",
+        name: "hydride",
+        charge: -1,
+    },
+    Ion {
+        formula: "F",
+        name: "fluoride",
+        charge: -1,
+    },
+    Ion {
+        formula: "Cl",
+        name: "chloride",
+        charge: -1,
+    },
+    Ion {
+        formula: "Br",
+        name: "bromide",
+        charge: -1,
+    },
+    Ion {
+        formula: "I",
+        name: "iodide",
+        charge: -1,
+    },
+    Ion {
+        formula: "O",
+        name: "oxide",
+        charge: -2,
+    },
+    Ion {
+        formula: "S",
+        name: "sulfide",
+        charge: -2,
+    },
+    Ion {
+        formula: "Se",
+        name: "selenide",
+        charge: -2,
+    },
+    Ion {
+        formula: "Te",
+        name: "telluride",
+        charge: -2,
+    },
+    Ion {
+        formula: "N",
+        name: "nitride",
+        charge: -3,
+    },
+    Ion {
+        formula: "P",
+        name: "phosphide",
+        charge: -3,
+    },
+    Ion {
+        formula: "As",
+        name: "arsenide",
+        charge: -3,
+    },
+    Ion {
+        formula: "B",
+        name: "boride",
+        charge: -3,
+    },
+    Ion {
+        formula: "C",
+        name: "carbide",
+        charge: -4,
+    },
+];
+
+/// Alternative names of polyatomic ions.
+const ALTERNATIVE_NAMES: [(&str, &str); 6] = [
+    ("bicarbonate", "hydrogen carbonate"),
+    ("bisulfate", "hydrogen sulfate"),
+    ("bisulfite", "hydrogen sulfite"),
+    ("persulfate", "peroxodisulfate"),
+    ("ethanoate", "acetate"),
+    ("methanoate", "formate"),
+];
+
+/// Alternative formulas of polyatomic ions.
+const ALTERNATIVE_FORMULAS: [(&str, &str); 3] =
+    [("C2H3O2", "CH3COO"), ("CH3CO2", "CH3COO"), ("HCO2", "HCOO")];
+
+/// Returns the polyatomic ion with the given formula, as written in `POLYATOMIC_IONS`.
+#[must_use]
+pub fn polyatomic_ion_by_formula(formula: &str) -> Option<&'static Ion> {
+    let formula = ALTERNATIVE_FORMULAS
+        .iter()
+        .find(|(alternative_formula, _)| *alternative_formula == formula)
+        .map_or(formula, |(_, formula)| formula);
+
+    POLYATOMIC_IONS.iter().find(|ion| ion.formula == formula)
+}
+
+/// Returns the polyatomic ion with the given name.
+/// The name is case insensitive and alternative names such as bicarbonate are accepted.
+/// The british spelling "sulph" is accepted too.
+#[must_use]
+pub fn polyatomic_ion_by_name(name: &str) -> Option<&'static Ion> {
+    let lowercase_name = name.trim().to_lowercase().replace("sulph", "sulf");
+
+    let name = ALTERNATIVE_NAMES
+        .iter()
+        .find(|(alternative_name, _)| *alternative_name == lowercase_name)
+        .map_or(lowercase_name.as_str(), |(_, name)| name);
+
+    POLYATOMIC_IONS.iter().find(|ion| ion.name == name)
+}
+
+/// Returns the monatomic anion of the given element symbol.
+#[must_use]
+pub fn monatomic_anion_by_symbol(symbol: &str) -> Option<&'static Ion> {
+    MONATOMIC_ANIONS.iter().find(|ion| ion.formula == symbol)
+}
+
+/// Returns the monatomic anion with the given name.
+/// The name is case insensitive and the british spelling "sulph" is accepted.
+#[must_use]
+pub fn monatomic_anion_by_name(name: &str) -> Option<&'static Ion> {
+    let name = name.trim().to_lowercase().replace("sulph", "sulf");
+
+    MONATOMIC_ANIONS.iter().find(|ion| ion.name == name)
+}
+
+#[cfg(test)]
+#[path = "ion_tests.rs"]
+mod ion_tests;
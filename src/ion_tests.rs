@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::formula::to_empirical_formula;
+    use crate::ion::*;
+
+    #[test]
+    fn test_ion_formulas() {
+        for ion in POLYATOMIC_IONS.iter().chain(MONATOMIC_ANIONS.iter()) {
+            assert!(
+                to_empirical_formula(ion.formula).is_ok(),
+                "-> error {}",
+                ion.formula
+            );
+            assert_ne!(ion.charge, 0, "-> error {}", ion.formula);
+        }
+    }
+
+    #[test]
+    fn test_polyatomic_ion_by_formula() {
+        assert_eq!(polyatomic_ion_by_formula("SO4").unwrap().name, "sulfate");
+        assert_eq!(polyatomic_ion_by_formula("C2H3O2").unwrap().name, "acetate");
+        assert!(polyatomic_ion_by_formula("XyZ").is_none());
+    }
+
+    #[test]
+    fn test_polyatomic_ion_by_name() {
+        assert_eq!(polyatomic_ion_by_name("Sulphate").unwrap().formula, "SO4");
+        assert_eq!(
+            polyatomic_ion_by_name("bicarbonate").unwrap().formula,
+            "HCO3"
+        );
+        assert_eq!(
+            polyatomic_ion_by_name("hydrogen carbonate").unwrap().charge,
+            -1
+        );
+        assert!(polyatomic_ion_by_name("chloride").is_none());
+    }
+
+    #[test]
+    fn test_monatomic_anion() {
+        assert_eq!(monatomic_anion_by_symbol("Cl").unwrap().name, "chloride");
+        assert_eq!(monatomic_anion_by_name("sulphide").unwrap().formula, "S");
+        assert!(monatomic_anion_by_symbol("Na").is_none());
+    }
+}
@@ -0,0 +1,190 @@
+use std::ops::Range;
+
+use crate::{
+    element::{Element, element_by_symbol},
+    formula::split_formula_charge,
+};
+
+/// Separators between the parts of an adduct, as in `CuSO4·5H2O`.
+pub const ADDUCT_SEPARATORS: [char; 6] = ['·', '.', '•', '∙', '⋅', '*'];
+
+/// The kind of a formula token.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenKind {
+    /// A known element symbol, as `Na`.
+    Element(&'static Element),
+    /// An atom or a group count, as `2` in `H2O` or `(SO4)3`.
+    Count(usize),
+    /// The leading multiplier of a formula or of an adduct part, as `5` in `CuSO4·5H2O`.
+    Multiplier(usize),
+    /// A decimal leading multiplier, as `0.5` in `CaSO4·0.5H2O`.
+    FractionalMultiplier(f64),
+    /// `(` or `[`.
+    OpenBracket(char),
+    /// `)` or `]`.
+    CloseBracket(char),
+    /// One of `ADDUCT_SEPARATORS`.
+    AdductSeparator,
+    /// The charge at the end of the formula, see `split_formula_charge`.
+    Charge(isize),
+    /// A bond of a linear formula: `-`, `=`, `#` or `≡`.
+    Bond,
+    Whitespace,
+    /// Anything else: unknown element symbols, stray chars or too large numbers.
+    Unknown,
+}
+
+/// A formula token with its byte span in the input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    pub span: Range<usize>,
+}
+
+/// A streaming lexer over a formula.
+/// The lexer never fails: invalid parts are returned as `TokenKind::Unknown` and the lexing goes on.
+/// Built with `tokenize`.
+#[derive(Debug, Clone)]
+pub struct FormulaLexer<'a> {
+    input: &'a str,
+    position: usize,
+    /// Start of the charge at the end of the input, the input length if none.
+    charge_start: usize,
+    charge: isize,
+    /// True at the beginning of the formula or of an adduct part, where digits are multipliers.
+    expect_multiplier: bool,
+}
+
+/// Returns a streaming lexer over a formula.
+/// Example: `CuSO4·5H2O` gives Cu, S, O, 4, ·, 5 (multiplier), H, 2, O.
+#[must_use]
+pub fn tokenize(formula: &str) -> FormulaLexer<'_> {
+    let (formula_without_charge, charge) = split_formula_charge(formula);
+
+    let charge_start = if charge == 0 {
+        formula.len()
+    } else {
+        // The charge may be separated by whitespaces, as in `SO4 2-`.
+        let rest = &formula[formula_without_charge.len()..];
+        formula_without_charge.len() + (rest.len() - rest.trim_start().len())
+    };
+
+    FormulaLexer {
+        input: formula,
+        position: 0,
+        charge_start,
+        charge,
+        expect_multiplier: true,
+    }
+}
+
+impl<'a> FormulaLexer<'a> {
+    /// Builds the token from the current position to `end` and moves the position.
+    fn token(&mut self, kind: TokenKind, end: usize) -> Token<'a> {
+        let span = self.position..end;
+        self.position = end;
+
+        Token {
+            kind,
+            text: &self.input[span.clone()],
+            span,
+        }
+    }
+}
+
+impl<'a> Iterator for FormulaLexer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.input.len() {
+            return None;
+        }
+
+        // The charge, followed by trailing whitespaces.
+        if self.position == self.charge_start {
+            let end = self.input.trim_end().len();
+            return Some(self.token(TokenKind::Charge(self.charge), end));
+        }
+        if self.position > self.charge_start {
+            let end = self.input.len();
+            return Some(self.token(TokenKind::Whitespace, end));
+        }
+
+        let rest = &self.input[self.position..self.charge_start];
+
+        let mut chars = rest.chars();
+        let current_char = chars.next()?;
+        let char_end = self.position + current_char.len_utf8();
+
+        let token = match current_char {
+            'A'..='Z' => {
+                let end = match chars.next() {
+                    Some(next_char) if next_char.is_ascii_lowercase() => char_end + 1,
+                    _ => char_end,
+                };
+
+                match element_by_symbol(&self.input[self.position..end]) {
+                    Some(element) => self.token(TokenKind::Element(element), end),
+                    None => self.token(TokenKind::Unknown, end),
+                }
+            }
+            '0'..='9' => {
+                let digit_count = rest.bytes().take_while(u8::is_ascii_digit).count();
+                let end = self.position + digit_count;
+
+                // A decimal multiplier, the dot is not an adduct separator.
+                let decimal_count = rest[digit_count..].strip_prefix('.').map_or(0, |decimals| {
+                    decimals.bytes().take_while(u8::is_ascii_digit).count()
+                });
+                if self.expect_multiplier && decimal_count > 0 {
+                    let end = end + 1 + decimal_count;
+
+                    let token = match self.input[self.position..end].parse::<f64>() {
+                        Ok(number) => self.token(TokenKind::FractionalMultiplier(number), end),
+                        Err(_) => self.token(TokenKind::Unknown, end),
+                    };
+                    self.expect_multiplier = false;
+
+                    return Some(token);
+                }
+
+                match self.input[self.position..end].parse::<usize>() {
+                    Ok(number) if self.expect_multiplier => {
+                        self.token(TokenKind::Multiplier(number), end)
+                    }
+                    Ok(number) => self.token(TokenKind::Count(number), end),
+                    Err(_) => self.token(TokenKind::Unknown, end),
+                }
+            }
+            '(' | '[' => self.token(TokenKind::OpenBracket(current_char), char_end),
+            ')' | ']' => self.token(TokenKind::CloseBracket(current_char), char_end),
+            '-' | '=' | '#' | '≡' => self.token(TokenKind::Bond, char_end),
+            c if ADDUCT_SEPARATORS.contains(&c) => {
+                let token = self.token(TokenKind::AdductSeparator, char_end);
+                self.expect_multiplier = true;
+                return Some(token);
+            }
+            c if c.is_whitespace() => {
+                let whitespace_length: usize = rest
+                    .chars()
+                    .take_while(|c| c.is_whitespace())
+                    .map(char::len_utf8)
+                    .sum();
+                let end = self.position + whitespace_length;
+
+                // Whitespaces do not end the multiplier position.
+                return Some(self.token(TokenKind::Whitespace, end));
+            }
+            _ => self.token(TokenKind::Unknown, char_end),
+        };
+
+        self.expect_multiplier = false;
+
+        Some(token)
+    }
+}
+
+#[cfg(test)]
+#[path = "lexer_tests.rs"]
+mod lexer_tests;
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::{element::element_by_symbol, lexer::*};
+
+    fn init_logger() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    fn texts(formula: &str) -> Vec<&str> {
+        tokenize(formula).map(|token| token.text).collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        init_logger();
+
+        let tokens: Vec<Token> = tokenize("Fe2(SO4)3").collect();
+        assert_eq!(tokens.len(), 8);
+        assert_eq!(
+            tokens[0],
+            Token {
+                kind: TokenKind::Element(element_by_symbol("Fe").unwrap()),
+                text: "Fe",
+                span: 0..2,
+            }
+        );
+        assert_eq!(tokens[1].kind, TokenKind::Count(2));
+        assert_eq!(tokens[2].kind, TokenKind::OpenBracket('('));
+        assert_eq!(tokens[6].kind, TokenKind::CloseBracket(')'));
+        assert_eq!(tokens[7].kind, TokenKind::Count(3));
+        assert_eq!(tokens[7].span, 8..9);
+
+        // Adducts and multipliers.
+        let kinds: Vec<TokenKind> = tokenize("CuSO4·5H2O").map(|token| token.kind).collect();
+        assert_eq!(kinds[4], TokenKind::AdductSeparator);
+        assert_eq!(kinds[5], TokenKind::Multiplier(5));
+        assert_eq!(kinds[7], TokenKind::Count(2));
+        assert_eq!(texts("CuSO4·5H2O")[4], "·");
+        assert_eq!(
+            tokenize("2NaCl").next().unwrap().kind,
+            TokenKind::Multiplier(2)
+        );
+
+        let kinds: Vec<TokenKind> = tokenize("CaSO4·0.5H2O").map(|token| token.kind).collect();
+        assert_eq!(kinds[4], TokenKind::AdductSeparator);
+        assert_eq!(kinds[5], TokenKind::FractionalMultiplier(0.5));
+        assert_eq!(kinds.len(), 9);
+        assert_eq!(texts("CaSO4·0.5H2O")[5], "0.5");
+        assert_eq!(texts("C5H5N.HCl")[5], ".");
+
+        // Charges.
+        let tokens: Vec<Token> = tokenize("NH4+").collect();
+        assert_eq!(tokens[3].kind, TokenKind::Charge(1));
+        assert_eq!(tokens[3].span, 3..4);
+        assert_eq!(texts("SO4 2- "), vec!["S", "O", "4", " ", "2-", " "]);
+        assert_eq!(
+            tokenize("SO4 2-").nth(4).unwrap().kind,
+            TokenKind::Charge(-2)
+        );
+        assert_eq!(texts("SO4(2-)"), vec!["S", "O", "4", "(2-)"]);
+        assert_eq!(texts("[Fe(CN)6]4-").last().unwrap(), &"4-");
+        assert_eq!(texts("SO42-"), vec!["S", "O", "42", "-"]);
+
+        // Bonds in linear formulas.
+        let kinds: Vec<TokenKind> = tokenize("CH2=CH2").map(|token| token.kind).collect();
+        assert_eq!(kinds[3], TokenKind::Bond);
+    }
+
+    #[test]
+    fn test_tokenize_errors() {
+        init_logger();
+
+        // The lexer goes on after errors.
+        let tokens: Vec<Token> = tokenize("CxH4!Na").collect();
+        assert_eq!(tokens[0].kind, TokenKind::Unknown);
+        assert_eq!(tokens[0].text, "Cx");
+        assert_eq!(
+            tokens[1].kind,
+            TokenKind::Element(element_by_symbol("H").unwrap())
+        );
+        assert_eq!(tokens[3].kind, TokenKind::Unknown);
+        assert_eq!(tokens[3].span, 4..5);
+        assert_eq!(
+            tokens[4].kind,
+            TokenKind::Element(element_by_symbol("Na").unwrap())
+        );
+
+        // Spans are byte offsets.
+        let tokens: Vec<Token> = tokenize("H₂O").collect();
+        assert_eq!(tokens[1].kind, TokenKind::Unknown);
+        assert_eq!(tokens[1].span, 1..4);
+        assert_eq!(tokens[2].span, 4..5);
+
+        assert_eq!(
+            tokenize("C99999999999999999999999").nth(1).unwrap().kind,
+            TokenKind::Unknown
+        );
+
+        assert_eq!(tokenize("").count(), 0);
+    }
+}
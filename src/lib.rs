@@ -12,9 +12,29 @@
     clippy::too_many_lines
 )]
 
+pub mod adduct;
+pub mod analysis;
 pub mod casbin;
 pub mod casnumber;
 pub mod cenumber;
+mod checksum;
+pub mod classification;
+pub mod composition;
 pub mod define;
+pub mod element;
 pub mod formula;
+pub mod hydrate;
+pub mod indexnumber;
+pub mod ion;
+pub mod lexer;
+pub mod molfile;
+pub mod nomenclature;
+pub mod oxidation;
+pub mod registry;
+pub mod scanner;
+pub mod stoichiometry;
 pub mod string;
+pub mod unnumber;
+
+#[cfg(test)]
+mod test_helpers;
@@ -0,0 +1,619 @@
+use log::debug;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter},
+    ops::Range,
+};
+
+use crate::{
+    casnumber::normalize_cas_number,
+    element::{Element, element_by_atomic_number, element_by_symbol},
+    formula::{empirical_formula_from_atom_counts, molar_mass_from_atom_counts},
+    scanner::{IdentifierKind, scan_identifiers},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MolfileError {
+    EmptyMolfile,
+    MissingCountsLine,
+    InvalidCountsLine(String),
+    UnsupportedVersion(String),
+    TruncatedBlock(String),
+    InvalidAtomLine(String),
+    InvalidBondLine(String),
+    InvalidPropertyLine(String),
+    UnknownAtom(String),
+    AtomIndexOutOfRange(usize),
+    InvalidCasNumber(String),
+}
+
+impl Display for MolfileError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            MolfileError::EmptyMolfile => write!(f, "empty molfile"),
+            MolfileError::MissingCountsLine => write!(f, "missing counts line"),
+            MolfileError::InvalidCountsLine(s) => write!(f, "invalid counts line {s}"),
+            MolfileError::UnsupportedVersion(s) => write!(f, "unsupported version {s}"),
+            MolfileError::TruncatedBlock(s) => write!(f, "truncated {s} block"),
+            MolfileError::InvalidAtomLine(s) => write!(f, "invalid atom line {s}"),
+            MolfileError::InvalidBondLine(s) => write!(f, "invalid bond line {s}"),
+            MolfileError::InvalidPropertyLine(s) => write!(f, "invalid property line {s}"),
+            MolfileError::UnknownAtom(s) => write!(f, "unknown atom {s}"),
+            MolfileError::AtomIndexOutOfRange(i) => write!(f, "atom index {i} out of range"),
+            MolfileError::InvalidCasNumber(s) => write!(f, "invalid CAS number {s}"),
+        }
+    }
+}
+
+impl std::error::Error for MolfileError {}
+
+/// A molecule read from a molfile or an SDF record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MolRecord {
+    /// The molecule name, from the header or a name data field.
+    pub name: String,
+    /// The empirical formula, in the same form as `to_empirical_formula`.
+    pub empirical_formula: String,
+    /// The molar mass in g/mol.
+    pub molar_mass: f64,
+    /// The net charge of the molecule.
+    pub charge: isize,
+    /// The isotope labels as (symbol, mass number), deuterium excepted.
+    pub isotopes: Vec<(String, u16)>,
+    /// The valid CAS numbers of the CAS data fields, normalized and without duplicates.
+    pub cas_numbers: Vec<String>,
+    /// The problems that do not invalidate the record, as an invalid CAS number.
+    pub warnings: Vec<MolfileError>,
+    /// The SDF data fields as (name, value).
+    pub data: Vec<(String, String)>,
+}
+
+/// An atom of the connection table.
+#[derive(Debug)]
+struct MolAtom {
+    element: &'static Element,
+    charge: isize,
+    mass_number: Option<u16>,
+    /// Total valence from the valence field, 0 for "zero valence".
+    valence: Option<usize>,
+    /// Sum of the bond orders, doubled to count aromatic bonds as 1.5.
+    doubled_bond_order_sum: usize,
+}
+
+/// Default valences used to compute the implicit hydrogens, by atomic number.
+const DEFAULT_VALENCES: [(u8, &[usize]); 16] = [
+    (5, &[3]),
+    (6, &[4]),
+    (7, &[3, 5]),
+    (8, &[2]),
+    (9, &[1]),
+    (14, &[4]),
+    (15, &[3, 5]),
+    (16, &[2, 4, 6]),
+    (17, &[1]),
+    (32, &[4]),
+    (33, &[3, 5]),
+    (34, &[2, 4, 6]),
+    (35, &[1]),
+    (51, &[3, 5]),
+    (52, &[2, 4, 6]),
+    (53, &[1]),
+];
+
+/// Names of the data fields used as molecule name when the header has none.
+const NAME_FIELDS: [&str; 4] = ["NAME", "IUPAC_NAME", "PUBCHEM_IUPAC_NAME", "SYNONYM"];
+
+/// Reads all the records of an SDF file.
+/// An invalid record gets its error at its position in the result.
+#[must_use]
+pub fn read_sdf(sdf: &str) -> Vec<Result<MolRecord, Box<dyn Error + Send + Sync>>> {
+    let mut records = Vec::new();
+    let mut record_lines: Vec<&str> = Vec::new();
+
+    for line in sdf.lines() {
+        if line.trim_end() == "$$$$" {
+            records.push(read_molfile_lines(&record_lines));
+            record_lines.clear();
+        } else {
+            record_lines.push(line);
+        }
+    }
+
+    // Last record without terminator.
+    if record_lines.iter().any(|line| !line.trim().is_empty()) {
+        records.push(read_molfile_lines(&record_lines));
+    }
+
+    debug!("read {} SDF records", records.len());
+
+    records
+}
+
+/// Reads an MDL molfile, V2000 or V3000, with its optional SDF data fields.
+pub fn read_molfile(molfile: &str) -> Result<MolRecord, Box<dyn Error + Send + Sync>> {
+    let lines: Vec<&str> = molfile.lines().collect();
+
+    read_molfile_lines(&lines)
+}
+
+fn read_molfile_lines(lines: &[&str]) -> Result<MolRecord, Box<dyn Error + Send + Sync>> {
+    // Rejecting empty records.
+    if lines.iter().all(|line| line.trim().is_empty()) {
+        return Err(Box::new(MolfileError::EmptyMolfile));
+    }
+
+    // Header: name, program, comment and then the counts line.
+    let Some(counts_line) = lines.get(3) else {
+        return Err(Box::new(MolfileError::MissingCountsLine));
+    };
+
+    let (atoms, end_index) = if counts_line.contains("V3000") {
+        read_v3000_ctab(lines)?
+    } else if counts_line.contains("V2000") || !counts_line.contains('V') {
+        // Old molfiles may omit the version.
+        read_v2000_ctab(lines, counts_line)?
+    } else {
+        return Err(Box::new(MolfileError::UnsupportedVersion(
+            counts_line.to_string(),
+        )));
+    };
+
+    let data = read_data_fields(&lines[end_index..]);
+
+    // Building the atom counts with the implicit hydrogens.
+    let mut atom_count_map: HashMap<String, usize> = HashMap::new();
+    let mut isotopes: Vec<(String, u16)> = Vec::new();
+    let mut isotope_mass_delta = 0.0;
+    let mut charge: isize = 0;
+
+    for atom in &atoms {
+        charge += atom.charge;
+
+        let symbol = match atom.mass_number {
+            Some(2) if atom.element.atomic_number == 1 => "D",
+            Some(mass_number) => {
+                isotopes.push((atom.element.symbol.to_string(), mass_number));
+                isotope_mass_delta += f64::from(mass_number) - atom.element.atomic_weight;
+                atom.element.symbol
+            }
+            None => atom.element.symbol,
+        };
+        *atom_count_map.entry(symbol.to_string()).or_insert(0) += 1;
+
+        let implicit_hydrogens = implicit_hydrogens(atom);
+        if implicit_hydrogens > 0 {
+            *atom_count_map.entry("H".to_string()).or_insert(0) += implicit_hydrogens;
+        }
+    }
+    debug!("atom_count_map: {atom_count_map:?}");
+
+    // CAS numbers, found by the scanner as the values may hold other text: `CAS 64-17-5` or
+    // `64-17-5 (anhydrous)`. An invalid CAS number is a warning, not an error.
+    let mut cas_numbers: Vec<String> = Vec::new();
+    let mut warnings: Vec<MolfileError> = Vec::new();
+    for (field_name, value) in &data {
+        if !field_name.to_uppercase().contains("CAS") || value.trim().is_empty() {
+            continue;
+        }
+
+        let mut has_cas_number = false;
+        for identifier_match in scan_identifiers(value)
+            .into_iter()
+            .filter(|identifier_match| identifier_match.kind == IdentifierKind::CasNumber)
+        {
+            has_cas_number = true;
+
+            match normalize_cas_number(identifier_match.text) {
+                Ok(normalized) if identifier_match.is_valid => {
+                    let cas_number = normalized.cas_number.to_string();
+                    if !cas_numbers.contains(&cas_number) {
+                        cas_numbers.push(cas_number);
+                    }
+                }
+                _ => warnings.push(MolfileError::InvalidCasNumber(
+                    identifier_match.text.to_string(),
+                )),
+            }
+        }
+
+        if !has_cas_number {
+            warnings.push(MolfileError::InvalidCasNumber(value.clone()));
+        }
+    }
+    debug!("cas_numbers: {cas_numbers:?} warnings: {warnings:?}");
+
+    // Name from the header or from the data fields.
+    let mut name = lines[0].trim().to_string();
+    if name.is_empty()
+        && let Some((_, value)) = data
+            .iter()
+            .find(|(field_name, _)| NAME_FIELDS.contains(&field_name.to_uppercase().as_str()))
+    {
+        name.clone_from(value);
+    }
+
+    Ok(MolRecord {
+        name,
+        empirical_formula: empirical_formula_from_atom_counts(&atom_count_map),
+        molar_mass: molar_mass_from_atom_counts(&atom_count_map)? + isotope_mass_delta,
+        charge,
+        isotopes,
+        cas_numbers,
+        warnings,
+        data,
+    })
+}
+
+/// Reads a V2000 connection table.
+/// Returns the atoms and the index of the line following the properties block.
+fn read_v2000_ctab(
+    lines: &[&str],
+    counts_line: &str,
+) -> Result<(Vec<MolAtom>, usize), Box<dyn Error + Send + Sync>> {
+    let (Some(atom_count), Some(bond_count)) = (
+        parse_fixed_width(counts_line, 0),
+        parse_fixed_width(counts_line, 3),
+    ) else {
+        return Err(Box::new(MolfileError::InvalidCountsLine(
+            counts_line.to_string(),
+        )));
+    };
+    debug!("atom_count: {atom_count} bond_count: {bond_count}");
+
+    let atom_start = 4;
+    let bond_start = atom_start + atom_count;
+    let properties_start = bond_start + bond_count;
+
+    if lines.len() < properties_start {
+        return Err(Box::new(MolfileError::TruncatedBlock(
+            "connection table".to_string(),
+        )));
+    }
+
+    // Atom block, fixed width columns: coordinates may run together as in `-1000.0000-1000.0000`.
+    let mut atoms: Vec<MolAtom> = Vec::with_capacity(atom_count);
+    for line in &lines[atom_start..bond_start] {
+        let is_valid_coordinates = [0..10, 10..20, 20..30]
+            .into_iter()
+            .all(|columns| fixed_width_field(line, columns).parse::<f64>().is_ok());
+        if !is_valid_coordinates || line.len() < 32 {
+            return Err(Box::new(MolfileError::InvalidAtomLine((*line).to_string())));
+        }
+
+        let element = parse_atom_symbol(fixed_width_field(line, 31..34))?;
+
+        // Mass difference from the rounded atomic weight.
+        let mass_number = match optional_fixed_width_field(line, 34..36).map(str::parse::<i32>) {
+            Some(Ok(0)) | None => None,
+            Some(Ok(mass_difference)) => {
+                #[allow(clippy::cast_possible_truncation)]
+                let rounded_weight = element.atomic_weight.round() as i32;
+                u16::try_from(rounded_weight + mass_difference).ok()
+            }
+            Some(Err(_)) => {
+                return Err(Box::new(MolfileError::InvalidAtomLine((*line).to_string())));
+            }
+        };
+
+        // Charge code.
+        let charge = match optional_fixed_width_field(line, 36..39).map(str::parse::<u8>) {
+            Some(Ok(1)) => 3,
+            Some(Ok(2)) => 2,
+            Some(Ok(3)) => 1,
+            Some(Ok(5)) => -1,
+            Some(Ok(6)) => -2,
+            Some(Ok(7)) => -3,
+            Some(Ok(_)) | None => 0,
+            Some(Err(_)) => {
+                return Err(Box::new(MolfileError::InvalidAtomLine((*line).to_string())));
+            }
+        };
+
+        // Valence field: 0 for default, 15 for zero valence.
+        let valence = match optional_fixed_width_field(line, 48..51).map(str::parse::<usize>) {
+            Some(Ok(15)) => Some(0),
+            Some(Ok(valence @ 1..=14)) => Some(valence),
+            _ => None,
+        };
+
+        atoms.push(MolAtom {
+            element,
+            charge,
+            mass_number,
+            valence,
+            doubled_bond_order_sum: 0,
+        });
+    }
+
+    // Bond block.
+    for line in &lines[bond_start..properties_start] {
+        let (Some(first_atom), Some(second_atom), Some(bond_type)) = (
+            parse_fixed_width(line, 0),
+            parse_fixed_width(line, 3),
+            parse_fixed_width(line, 6),
+        ) else {
+            return Err(Box::new(MolfileError::InvalidBondLine((*line).to_string())));
+        };
+
+        add_bond(&mut atoms, first_atom, second_atom, bond_type)?;
+    }
+
+    // Properties block, CHG and ISO lines replace the atom block values.
+    let mut end_index = properties_start;
+    let mut charges_reset = false;
+    let mut isotopes_reset = false;
+
+    for (index, line) in lines.iter().enumerate().skip(properties_start) {
+        end_index = index + 1;
+
+        if line.starts_with("M  END") {
+            break;
+        }
+
+        let is_charge_line = line.starts_with("M  CHG");
+        let is_isotope_line = line.starts_with("M  ISO");
+        if !is_charge_line && !is_isotope_line {
+            continue;
+        }
+
+        if is_charge_line && !charges_reset {
+            for atom in &mut atoms {
+                atom.charge = 0;
+            }
+            charges_reset = true;
+        }
+        if is_isotope_line && !isotopes_reset {
+            for atom in &mut atoms {
+                atom.mass_number = None;
+            }
+            isotopes_reset = true;
+        }
+
+        // M  CHG  n aaa vvv aaa vvv...
+        let values: Vec<&str> = line.split_whitespace().skip(3).collect();
+        if !values.len().is_multiple_of(2) {
+            return Err(Box::new(MolfileError::InvalidPropertyLine(
+                (*line).to_string(),
+            )));
+        }
+
+        for pair in values.chunks(2) {
+            let (Ok(atom_index), Ok(value)) = (pair[0].parse::<usize>(), pair[1].parse::<isize>())
+            else {
+                return Err(Box::new(MolfileError::InvalidPropertyLine(
+                    (*line).to_string(),
+                )));
+            };
+
+            let Some(atom) = atom_index
+                .checked_sub(1)
+                .and_then(|index| atoms.get_mut(index))
+            else {
+                return Err(Box::new(MolfileError::AtomIndexOutOfRange(atom_index)));
+            };
+
+            if is_charge_line {
+                atom.charge = value;
+            } else {
+                atom.mass_number = u16::try_from(value).ok();
+            }
+        }
+    }
+
+    Ok((atoms, end_index))
+}
+
+/// Reads a V3000 connection table.
+/// Returns the atoms and the index of the line following the M  END line.
+fn read_v3000_ctab(lines: &[&str]) -> Result<(Vec<MolAtom>, usize), Box<dyn Error + Send + Sync>> {
+    let mut atoms: Vec<MolAtom> = Vec::new();
+    let mut block = "";
+    let mut end_index = lines.len();
+    let mut is_ended = false;
+    // V3000 lines ending with - continue on the next line.
+    let mut continued_line = String::new();
+
+    for (index, line) in lines.iter().enumerate().skip(4) {
+        if line.starts_with("M  END") {
+            end_index = index + 1;
+            is_ended = true;
+            break;
+        }
+
+        let Some(content) = line.strip_prefix("M  V30 ") else {
+            continue;
+        };
+
+        if let Some(content) = content.strip_suffix('-') {
+            continued_line.push_str(content);
+            continue;
+        }
+        continued_line.push_str(content);
+        let content = std::mem::take(&mut continued_line);
+        let fields: Vec<&str> = content.split_whitespace().collect();
+
+        match fields.as_slice() {
+            ["BEGIN", "ATOM", ..] => block = "ATOM",
+            ["BEGIN", "BOND", ..] => block = "BOND",
+            ["BEGIN" | "END", ..] => block = "",
+            _ if block == "ATOM" => {
+                if fields.len() < 5 {
+                    return Err(Box::new(MolfileError::InvalidAtomLine(content.clone())));
+                }
+
+                let element = parse_atom_symbol(fields[1])?;
+                let mut atom = MolAtom {
+                    element,
+                    charge: 0,
+                    mass_number: None,
+                    valence: None,
+                    doubled_bond_order_sum: 0,
+                };
+
+                for property in &fields[5..] {
+                    let Some((key, value)) = property.split_once('=') else {
+                        continue;
+                    };
+                    match key {
+                        "CHG" => {
+                            let Ok(charge) = value.parse() else {
+                                return Err(Box::new(MolfileError::InvalidAtomLine(
+                                    content.clone(),
+                                )));
+                            };
+                            atom.charge = charge;
+                        }
+                        "MASS" => atom.mass_number = value.parse().ok(),
+                        "VAL" => {
+                            atom.valence = match value.parse::<isize>() {
+                                Ok(-1) => Some(0),
+                                Ok(valence) => usize::try_from(valence).ok(),
+                                Err(_) => None,
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+
+                atoms.push(atom);
+            }
+            _ if block == "BOND" => {
+                let parsed_fields: Vec<Option<usize>> = fields
+                    .iter()
+                    .take(4)
+                    .map(|field| field.parse::<usize>().ok())
+                    .collect();
+                let [_, Some(bond_type), Some(first_atom), Some(second_atom)] =
+                    parsed_fields.as_slice()
+                else {
+                    return Err(Box::new(MolfileError::InvalidBondLine(content.clone())));
+                };
+
+                add_bond(&mut atoms, *first_atom, *second_atom, *bond_type)?;
+            }
+            _ => (),
+        }
+    }
+
+    if !is_ended {
+        return Err(Box::new(MolfileError::TruncatedBlock("CTAB".to_string())));
+    }
+
+    Ok((atoms, end_index))
+}
+
+/// Reads the SDF data fields following the connection table.
+fn read_data_fields(lines: &[&str]) -> Vec<(String, String)> {
+    let mut data: Vec<(String, String)> = Vec::new();
+    let mut current_field: Option<(String, Vec<&str>)> = None;
+
+    for line in lines {
+        if line.starts_with('>') {
+            // > <FIELD_NAME> or >  25  <FIELD_NAME>
+            if let Some((field_name, values)) = current_field.take() {
+                data.push((field_name, values.join("\n")));
+            }
+            let field_name = line
+                .split_once('<')
+                .and_then(|(_, rest)| rest.split_once('>'))
+                .map_or("", |(field_name, _)| field_name);
+            current_field = Some((field_name.to_string(), Vec::new()));
+        } else if line.trim().is_empty() {
+            if let Some((field_name, values)) = current_field.take() {
+                data.push((field_name, values.join("\n")));
+            }
+        } else if let Some((_, values)) = &mut current_field {
+            values.push(line.trim_end());
+        }
+    }
+
+    if let Some((field_name, values)) = current_field {
+        data.push((field_name, values.join("\n")));
+    }
+
+    debug!("data: {data:?}");
+
+    data
+}
+
+/// Adds a bond between two atoms, with 1-based indexes.
+fn add_bond(
+    atoms: &mut [MolAtom],
+    first_atom: usize,
+    second_atom: usize,
+    bond_type: usize,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    // Single, double, triple and aromatic bonds. Query bonds count as single bonds.
+    let doubled_bond_order = match bond_type {
+        2 => 4,
+        3 => 6,
+        4 => 3,
+        _ => 2,
+    };
+
+    for atom_index in [first_atom, second_atom] {
+        let Some(atom) = atom_index
+            .checked_sub(1)
+            .and_then(|index| atoms.get_mut(index))
+        else {
+            return Err(Box::new(MolfileError::AtomIndexOutOfRange(atom_index)));
+        };
+        atom.doubled_bond_order_sum += doubled_bond_order;
+    }
+
+    Ok(())
+}
+
+/// Returns the number of implicit hydrogens of an atom.
+/// A charged atom gets the valences of its isoelectronic element: N+ as C, O- as F.
+fn implicit_hydrogens(atom: &MolAtom) -> usize {
+    let bond_order_sum = atom.doubled_bond_order_sum.div_ceil(2);
+
+    if let Some(valence) = atom.valence {
+        return valence.saturating_sub(bond_order_sum);
+    }
+
+    let Some(isoelectronic_element) = isize::from(atom.element.atomic_number)
+        .checked_sub(atom.charge)
+        .and_then(|atomic_number| u8::try_from(atomic_number).ok())
+        .and_then(element_by_atomic_number)
+    else {
+        return 0;
+    };
+
+    DEFAULT_VALENCES
+        .iter()
+        .find(|(atomic_number, _)| *atomic_number == isoelectronic_element.atomic_number)
+        .and_then(|(_, valences)| valences.iter().find(|valence| **valence >= bond_order_sum))
+        .map_or(0, |valence| valence - bond_order_sum)
+}
+
+/// Returns the element of an atom symbol, rejecting query atoms and pseudo atoms.
+fn parse_atom_symbol(symbol: &str) -> Result<&'static Element, Box<dyn Error + Send + Sync>> {
+    match element_by_symbol(symbol) {
+        Some(element) => Ok(element),
+        None => Err(Box::new(MolfileError::UnknownAtom(symbol.to_string()))),
+    }
+}
+
+/// Parses a 3 chars wide number starting at the given position.
+fn parse_fixed_width(line: &str, start: usize) -> Option<usize> {
+    fixed_width_field(line, start..start + 3).parse().ok()
+}
+
+/// Returns the trimmed field in the given columns, empty if the line is too short.
+fn fixed_width_field(line: &str, columns: Range<usize>) -> &str {
+    line.get(columns.start.min(line.len())..columns.end.min(line.len()))
+        .unwrap_or_default()
+        .trim()
+}
+
+/// Returns the trimmed field in the given columns, `None` if it is empty.
+fn optional_fixed_width_field(line: &str, columns: Range<usize>) -> Option<&str> {
+    Some(fixed_width_field(line, columns)).filter(|field| !field.is_empty())
+}
+
+#[cfg(test)]
+#[path = "molfile_tests.rs"]
+mod molfile_tests;
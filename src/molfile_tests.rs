@@ -0,0 +1,343 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::molfile::*;
+
+    fn init_logger() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    const ETHANOL_V2000: &str = "ethanol
+  test
+
+  3  2  0  0  0  0  0  0  0  0999 V2000
+    0.0000    0.0000    0.0000 C   0  0  0  0  0  0  0  0  0  0  0  0
+    1.5000    0.0000    0.0000 C   0  0  0  0  0  0  0  0  0  0  0  0
+    2.0000    1.0000    0.0000 O   0  0  0  0  0  0  0  0  0  0  0  0
+  1  2  1  0
+  2  3  1  0
+M  END
+";
+
+    const SODIUM_ACETATE_V2000: &str = "sodium acetate
+  test
+
+  5  3  0  0  0  0  0  0  0  0999 V2000
+    0.0000    0.0000    0.0000 C   0  0  0  0  0  0  0  0  0  0  0  0
+    1.5000    0.0000    0.0000 C   0  0  0  0  0  0  0  0  0  0  0  0
+    2.0000    1.0000    0.0000 O   0  0  0  0  0  0  0  0  0  0  0  0
+    2.0000   -1.0000    0.0000 O   0  0  0  0  0  0  0  0  0  0  0  0
+    4.0000    0.0000    0.0000 Na  0  0  0  0  0  0  0  0  0  0  0  0
+  1  2  1  0
+  2  3  2  0
+  2  4  1  0
+M  CHG  2   4  -1   5   1
+M  END
+";
+
+    const CHLOROFORM_D_V2000: &str = "chloroform-d
+  test
+
+  5  4  0  0  0  0  0  0  0  0999 V2000
+    0.0000    0.0000    0.0000 C   0  0  0  0  0  0  0  0  0  0  0  0
+    1.0000    0.0000    0.0000 Cl  0  0  0  0  0  0  0  0  0  0  0  0
+   -1.0000    0.0000    0.0000 Cl  0  0  0  0  0  0  0  0  0  0  0  0
+    0.0000    1.0000    0.0000 Cl  0  0  0  0  0  0  0  0  0  0  0  0
+    0.0000   -1.0000    0.0000 H   0  0  0  0  0  0  0  0  0  0  0  0
+  1  2  1  0
+  1  3  1  0
+  1  4  1  0
+  1  5  1  0
+M  ISO  1   5   2
+M  END
+";
+
+    const BENZENE_V3000: &str = "benzene
+  test
+
+  0  0  0     0  0            999 V3000
+M  V30 BEGIN CTAB
+M  V30 COUNTS 6 6 0 0 0
+M  V30 BEGIN ATOM
+M  V30 1 C 0 1.4 0 0
+M  V30 2 C 1.2 0.7 0 0
+M  V30 3 C 1.2 -0.7 0 0
+M  V30 4 C 0 -1.4 0 0
+M  V30 5 C -1.2 -0.7 0 0
+M  V30 6 C -1.2 0.7 0 -
+M  V30 0
+M  V30 END ATOM
+M  V30 BEGIN BOND
+M  V30 1 4 1 2
+M  V30 2 4 2 3
+M  V30 3 4 3 4
+M  V30 4 4 4 5
+M  V30 5 4 5 6
+M  V30 6 4 6 1
+M  V30 END BOND
+M  V30 END CTAB
+M  END
+";
+
+    const AMMONIUM_V3000: &str = "
+  test
+
+  0  0  0     0  0            999 V3000
+M  V30 BEGIN CTAB
+M  V30 COUNTS 1 0 0 0 0
+M  V30 BEGIN ATOM
+M  V30 1 N 0 0 0 0 CHG=1
+M  V30 END ATOM
+M  V30 END CTAB
+M  END
+> <NAME>
+ammonium
+
+";
+
+    #[test]
+    fn test_read_molfile() {
+        init_logger();
+
+        let record = read_molfile(ETHANOL_V2000).unwrap();
+        assert_eq!(record.name, "ethanol");
+        assert_eq!(record.empirical_formula, "C2H6O");
+        assert!((record.molar_mass - 46.069).abs() < 0.001);
+        assert_eq!(record.charge, 0);
+
+        let record = read_molfile(SODIUM_ACETATE_V2000).unwrap();
+        assert_eq!(record.empirical_formula, "C2H3NaO2");
+        assert_eq!(record.charge, 0);
+
+        let record = read_molfile(CHLOROFORM_D_V2000).unwrap();
+        assert_eq!(record.empirical_formula, "CCl3D");
+        assert!(record.isotopes.is_empty());
+
+        // Fixed width coordinates running together.
+        let record = read_molfile(&ETHANOL_V2000.replace(
+            "    1.5000    0.0000    0.0000 C ",
+            "-1000.0000-1000.0000-1000.0000 C ",
+        ))
+        .unwrap();
+        assert_eq!(record.empirical_formula, "C2H6O");
+
+        let record = read_molfile(BENZENE_V3000).unwrap();
+        assert_eq!(record.empirical_formula, "C6H6");
+        assert!((record.molar_mass - 78.114).abs() < 0.001);
+
+        let record = read_molfile(AMMONIUM_V3000).unwrap();
+        assert_eq!(record.name, "ammonium");
+        assert_eq!(record.empirical_formula, "H4N");
+        assert_eq!(record.charge, 1);
+    }
+
+    #[test]
+    fn test_read_molfile_isotopes() {
+        init_logger();
+
+        let methane_13c = "methane-13C
+  test
+
+  1  0  0  0  0  0  0  0  0  0999 V2000
+    0.0000    0.0000    0.0000 C   0  0  0  0  0  0  0  0  0  0  0  0
+M  ISO  1   1  13
+M  END
+";
+
+        let record = read_molfile(methane_13c).unwrap();
+        assert_eq!(record.empirical_formula, "CH4");
+        assert_eq!(record.isotopes, vec![("C".to_string(), 13)]);
+        assert!((record.molar_mass - 17.032).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_read_molfile_errors() {
+        init_logger();
+
+        assert_eq!(
+            *read_molfile("")
+                .unwrap_err()
+                .downcast::<MolfileError>()
+                .unwrap(),
+            MolfileError::EmptyMolfile
+        );
+        assert_eq!(
+            *read_molfile("name\n")
+                .unwrap_err()
+                .downcast::<MolfileError>()
+                .unwrap(),
+            MolfileError::MissingCountsLine
+        );
+        assert_eq!(
+            *read_molfile(&ETHANOL_V2000.replace(" O   0", " R   0"))
+                .unwrap_err()
+                .downcast::<MolfileError>()
+                .unwrap(),
+            MolfileError::UnknownAtom("R".to_string())
+        );
+        assert_eq!(
+            *read_molfile(&ETHANOL_V2000.replace("    2.0000    1.0000", "    2.0000    x.0000"))
+                .unwrap_err()
+                .downcast::<MolfileError>()
+                .unwrap(),
+            MolfileError::InvalidAtomLine(
+                "    2.0000    x.0000    0.0000 O   0  0  0  0  0  0  0  0  0  0  0  0".to_string()
+            )
+        );
+        assert_eq!(
+            *read_molfile(&AMMONIUM_V3000.replace("CHG=1", "CHG=x"))
+                .unwrap_err()
+                .downcast::<MolfileError>()
+                .unwrap(),
+            MolfileError::InvalidAtomLine("1 N 0 0 0 0 CHG=x".to_string())
+        );
+        assert_eq!(
+            *read_molfile(&ETHANOL_V2000.replace("  2  3  1  0", "  2  4  1  0"))
+                .unwrap_err()
+                .downcast::<MolfileError>()
+                .unwrap(),
+            MolfileError::AtomIndexOutOfRange(4)
+        );
+        assert_eq!(
+            *read_molfile(&ETHANOL_V2000.replace("  3  2  0", "  9  2  0"))
+                .unwrap_err()
+                .downcast::<MolfileError>()
+                .unwrap(),
+            MolfileError::TruncatedBlock("connection table".to_string())
+        );
+        assert_eq!(
+            *read_molfile(&ETHANOL_V2000.replace("V2000", "V4000"))
+                .unwrap_err()
+                .downcast::<MolfileError>()
+                .unwrap(),
+            MolfileError::UnsupportedVersion("  3  2  0  0  0  0  0  0  0  0999 V4000".to_string())
+        );
+        assert_eq!(
+            *read_molfile(&BENZENE_V3000.replace("M  END\n", ""))
+                .unwrap_err()
+                .downcast::<MolfileError>()
+                .unwrap(),
+            MolfileError::TruncatedBlock("CTAB".to_string())
+        );
+    }
+
+    #[test]
+    fn test_read_sdf() {
+        init_logger();
+
+        let sdf = format!(
+            "{ETHANOL_V2000}> <CAS>
+64-17-5
+
+> <NAME>
+ethanol
+
+$$$$
+{}$$$$
+{ETHANOL_V2000}> <CAS_NUMBER>
+64-17-6
+
+$$$$
+{ETHANOL_V2000}> <CAS>
+CAS 64-17-5 (anhydrous), 64–17–5; unknown
+
+> <CAS_OLD>
+n/a
+
+$$$$
+{BENZENE_V3000}",
+            ETHANOL_V2000.replace(" O   0", " R   0")
+        );
+
+        let records = read_sdf(&sdf);
+        assert_eq!(records.len(), 5);
+
+        let record = records[0].as_ref().unwrap();
+        assert_eq!(record.cas_numbers, vec!["64-17-5".to_string()]);
+        assert!(record.warnings.is_empty());
+        assert_eq!(
+            record.data,
+            vec![
+                ("CAS".to_string(), "64-17-5".to_string()),
+                ("NAME".to_string(), "ethanol".to_string())
+            ]
+        );
+
+        assert_eq!(
+            records[1].as_ref().unwrap_err().to_string(),
+            "unknown atom R"
+        );
+
+        // Invalid CAS numbers do not invalidate the record.
+        let record = records[2].as_ref().unwrap();
+        assert_eq!(record.empirical_formula, "C2H6O");
+        assert!(record.cas_numbers.is_empty());
+        assert_eq!(
+            record.warnings,
+            vec![MolfileError::InvalidCasNumber("64-17-6".to_string())]
+        );
+
+        let record = records[3].as_ref().unwrap();
+        assert!((record.molar_mass - 46.069).abs() < 0.001);
+        assert_eq!(record.cas_numbers, vec!["64-17-5".to_string()]);
+        assert_eq!(
+            record.warnings,
+            vec![MolfileError::InvalidCasNumber("n/a".to_string())]
+        );
+
+        assert_eq!(records[4].as_ref().unwrap().empirical_formula, "C6H6");
+
+        assert!(read_sdf("").is_empty());
+    }
+
+    #[test]
+    fn test_format_molfile_errors() {
+        assert_eq!(MolfileError::EmptyMolfile.to_string(), "empty molfile");
+        assert_eq!(
+            MolfileError::MissingCountsLine.to_string(),
+            "missing counts line"
+        );
+        assert_eq!(
+            MolfileError::InvalidCountsLine("x".to_string()).to_string(),
+            "invalid counts line x"
+        );
+        assert_eq!(
+            MolfileError::UnsupportedVersion("x".to_string()).to_string(),
+            "unsupported version x"
+        );
+        assert_eq!(
+            MolfileError::TruncatedBlock("CTAB".to_string()).to_string(),
+            "truncated CTAB block"
+        );
+        assert_eq!(
+            MolfileError::InvalidAtomLine("x".to_string()).to_string(),
+            "invalid atom line x"
+        );
+        assert_eq!(
+            MolfileError::InvalidBondLine("x".to_string()).to_string(),
+            "invalid bond line x"
+        );
+        assert_eq!(
+            MolfileError::InvalidPropertyLine("x".to_string()).to_string(),
+            "invalid property line x"
+        );
+        assert_eq!(
+            MolfileError::UnknownAtom("R".to_string()).to_string(),
+            "unknown atom R"
+        );
+        assert_eq!(
+            MolfileError::AtomIndexOutOfRange(4).to_string(),
+            "atom index 4 out of range"
+        );
+        assert_eq!(
+            MolfileError::InvalidCasNumber("x".to_string()).to_string(),
+            "invalid CAS number x"
+        );
+    }
+}
@@ -0,0 +1,565 @@
+use log::debug;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+use crate::{
+    element::{Element, element_by_name, element_by_symbol},
+    formula::gcd,
+    ion::{
+        Ion, POLYATOMIC_IONS, monatomic_anion_by_name, monatomic_anion_by_symbol,
+        polyatomic_ion_by_formula, polyatomic_ion_by_name,
+    },
+    string::{Transform, clean, from_roman, to_roman},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum NomenclatureError {
+    EmptyName,
+    EmptyFormula,
+    IncompleteName(String),
+    UnknownCation(String),
+    UnknownAnion(String),
+    UnknownHydrate(String),
+    MissingOxidationState(String),
+    InvalidOxidationState(String),
+    ChargesCanNotBeBalanced(String),
+    NoGreekPrefix(usize),
+    InvalidCount(String),
+}
+
+impl Display for NomenclatureError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            NomenclatureError::EmptyName => write!(f, "empty name"),
+            NomenclatureError::EmptyFormula => write!(f, "empty formula"),
+            NomenclatureError::IncompleteName(s) => write!(f, "incomplete name {s}"),
+            NomenclatureError::UnknownCation(s) => write!(f, "unknown cation {s}"),
+            NomenclatureError::UnknownAnion(s) => write!(f, "unknown anion {s}"),
+            NomenclatureError::UnknownHydrate(s) => write!(f, "unknown hydrate {s}"),
+            NomenclatureError::MissingOxidationState(s) => {
+                write!(f, "missing oxidation state for {s}")
+            }
+            NomenclatureError::InvalidOxidationState(s) => {
+                write!(f, "invalid oxidation state {s}")
+            }
+            NomenclatureError::ChargesCanNotBeBalanced(s) => {
+                write!(f, "charges can not be balanced for {s}")
+            }
+            NomenclatureError::NoGreekPrefix(count) => write!(f, "no greek prefix for {count}"),
+            NomenclatureError::InvalidCount(s) => write!(f, "invalid count {s}"),
+        }
+    }
+}
+
+impl std::error::Error for NomenclatureError {}
+
+/// Greek multiplying prefixes used in molecular compound and hydrate names.
+const GREEK_PREFIXES: [(&str, usize); 12] = [
+    ("mono", 1),
+    ("di", 2),
+    ("tri", 3),
+    ("tetra", 4),
+    ("penta", 5),
+    ("hexa", 6),
+    ("hepta", 7),
+    ("octa", 8),
+    ("nona", 9),
+    ("deca", 10),
+    ("undeca", 11),
+    ("dodeca", 12),
+];
+
+/// Highest oxidation state of the elements, as osmium in `OsO4`.
+const MAX_OXIDATION_STATE: i8 = 8;
+
+/// Elements named with greek prefixes when they are the first element of a binary compound.
+/// Hydrogen is not one of them: `HCl` is hydrogen chloride.
+const MOLECULAR_ELEMENTS: [&str; 16] = [
+    "As", "B", "Br", "C", "Cl", "F", "I", "Kr", "N", "O", "P", "S", "Se", "Si", "Te", "Xe",
+];
+
+/// Compounds better known by their trivial name.
+const TRIVIAL_NAMES: [(&str, &str); 3] = [
+    ("H2O", "water"),
+    ("H2O2", "hydrogen peroxide"),
+    ("NH3", "ammonia"),
+];
+
+/// Chars separating the water molecules in a hydrate formula.
+const HYDRATE_SEPARATORS: [char; 4] = ['·', '.', '•', '*'];
+
+/// A cation found while parsing a compound name.
+#[derive(Debug)]
+struct NamedCation {
+    formula: &'static str,
+    is_polyatomic: bool,
+    element: Option<&'static Element>,
+    oxidation_state: Option<i8>,
+    prefix_count: Option<usize>,
+}
+
+/// An anion found while parsing a compound name.
+#[derive(Debug)]
+struct NamedAnion {
+    ion: &'static Ion,
+    is_polyatomic: bool,
+    prefix_count: Option<usize>,
+}
+
+/// Converts the systematic name of a binary or common ternary inorganic compound into a formula.
+/// Stock notation, greek prefixes and hydrates are supported.
+/// Example: sodium chloride -> `NaCl`
+///          iron(III) sulfate -> `Fe2(SO4)3`
+///          dinitrogen pentoxide -> `N2O5`
+///          copper(II) sulfate pentahydrate -> `CuSO4·5H2O`
+pub fn name_to_formula(name: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let cleaned_name = clean(name, Transform::Lowercase).replace(" (", "(");
+
+    // Rejecting empty names.
+    if cleaned_name.is_empty() {
+        return Err(Box::new(NomenclatureError::EmptyName));
+    }
+
+    if let Some((formula, _)) = TRIVIAL_NAMES
+        .iter()
+        .find(|(_, trivial_name)| *trivial_name == cleaned_name)
+    {
+        return Ok((*formula).to_string());
+    }
+
+    let mut words: Vec<&str> = cleaned_name
+        .split(' ')
+        .filter(|word| *word != "anhydrous")
+        .collect();
+
+    // Hydrate suffix.
+    let mut water_count: Option<usize> = None;
+    if let Some(last_word) = words.last()
+        && let Some(prefix) = last_word.strip_suffix("hydrate")
+    {
+        if prefix.is_empty() {
+            water_count = Some(1);
+        } else if let Some((_, count)) = GREEK_PREFIXES.iter().find(|(p, _)| *p == prefix) {
+            water_count = Some(*count);
+        } else {
+            return Err(Box::new(NomenclatureError::UnknownHydrate(
+                (*last_word).to_string(),
+            )));
+        }
+        words.pop();
+    }
+    debug!("words: {words:?} water_count: {water_count:?}");
+
+    if words.len() < 2 {
+        return Err(Box::new(NomenclatureError::IncompleteName(cleaned_name)));
+    }
+
+    let cation = parse_cation_name(words[0])?;
+    let anion = parse_anion_name(&words[1..].join(" "))?;
+    debug!("cation: {cation:?} anion: {anion:?}");
+
+    let (cation_count, anion_count) =
+        if cation.prefix_count.is_some() || anion.prefix_count.is_some() {
+            // Molecular compound, the prefixes give the counts.
+            (
+                cation.prefix_count.unwrap_or(1),
+                anion.prefix_count.unwrap_or(1),
+            )
+        } else {
+            // Ionic compound, balancing the charges.
+            let cation_charge = if let Some(oxidation_state) = cation.oxidation_state {
+                oxidation_state
+            } else {
+                // Without stock notation the element must have a single usual oxidation state.
+                let positive_oxidation_states: Vec<i8> = cation
+                    .element
+                    .map(|element| element.positive_oxidation_states().collect())
+                    .unwrap_or_default();
+                if positive_oxidation_states.len() != 1 {
+                    return Err(Box::new(NomenclatureError::MissingOxidationState(
+                        words[0].to_string(),
+                    )));
+                }
+                positive_oxidation_states[0]
+            };
+
+            let cation_charge = usize::from(cation_charge.unsigned_abs());
+            let anion_charge = usize::from(anion.ion.charge.unsigned_abs());
+            let divisor = gcd(cation_charge, anion_charge);
+
+            (anion_charge / divisor, cation_charge / divisor)
+        };
+
+    let mut formula = format!(
+        "{}{}",
+        format_formula_part(cation.formula, cation.is_polyatomic, cation_count),
+        format_formula_part(anion.ion.formula, anion.is_polyatomic, anion_count)
+    );
+
+    match water_count {
+        Some(1) => formula.push_str("·H2O"),
+        Some(count) => {
+            formula.push('·');
+            formula.push_str(&count.to_string());
+            formula.push_str("H2O");
+        }
+        None => (),
+    }
+
+    debug!("formula: {formula}");
+
+    Ok(formula)
+}
+
+/// Converts the formula of a binary or common ternary inorganic compound into its systematic name.
+/// Example: `NaCl` -> sodium chloride
+///          `Fe2(SO4)3` -> iron(III) sulfate
+///          `N2O5` -> dinitrogen pentoxide
+///          `CuSO4·5H2O` -> copper(II) sulfate pentahydrate
+pub fn formula_to_name(formula: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let cleaned_formula: String = formula.chars().filter(|c| !c.is_whitespace()).collect();
+
+    // Rejecting empty formulas.
+    if cleaned_formula.is_empty() {
+        return Err(Box::new(NomenclatureError::EmptyFormula));
+    }
+
+    if let Some((_, trivial_name)) = TRIVIAL_NAMES
+        .iter()
+        .find(|(trivial_formula, _)| *trivial_formula == cleaned_formula)
+    {
+        return Ok((*trivial_name).to_string());
+    }
+
+    // Hydrate suffix.
+    let (main_formula, hydrate_name) = match cleaned_formula.split_once(HYDRATE_SEPARATORS) {
+        Some((main_formula, water)) => {
+            let (water_count, rest) = parse_count(water)?;
+            if rest != "H2O" {
+                return Err(Box::new(NomenclatureError::UnknownHydrate(
+                    water.to_string(),
+                )));
+            }
+            let Some((prefix, _)) = GREEK_PREFIXES.iter().find(|(_, c)| *c == water_count) else {
+                return Err(Box::new(NomenclatureError::UnknownHydrate(
+                    water.to_string(),
+                )));
+            };
+            (main_formula, Some(format!("{prefix}hydrate")))
+        }
+        None => (cleaned_formula.as_str(), None),
+    };
+
+    // Parsing the cation.
+    let (cation_formula, cation_count, anion_formula) = split_cation(main_formula)?;
+    debug!(
+        "cation_formula: {cation_formula} cation_count: {cation_count} anion_formula: {anion_formula}"
+    );
+
+    let cation_ion = polyatomic_ion_by_formula(cation_formula).filter(|ion| ion.charge > 0);
+    let cation_element = element_by_symbol(cation_formula);
+
+    // Parsing the anion, an element or a polyatomic ion.
+    let mut anions: Vec<(&Ion, bool, usize)> = Vec::new();
+    if let Some((element, rest)) = parse_symbol(anion_formula) {
+        let (count, rest) = parse_count(rest)?;
+        if rest.is_empty()
+            && let Some(ion) = monatomic_anion_by_symbol(element.symbol)
+        {
+            anions.push((ion, false, count));
+        }
+    }
+    if let Some(group) = anion_formula.strip_prefix('(')
+        && let Some((group, rest)) = group.split_once(')')
+    {
+        let (count, rest) = parse_count(rest)?;
+        if rest.is_empty()
+            && let Some(ion) = polyatomic_ion_by_formula(group).filter(|ion| ion.charge < 0)
+        {
+            anions.push((ion, true, count));
+        }
+    }
+    if let Some(ion) = polyatomic_ion_by_formula(anion_formula).filter(|ion| ion.charge < 0) {
+        anions.push((ion, true, 1));
+    }
+    debug!("anions: {anions:?}");
+
+    if anions.is_empty() {
+        return Err(Box::new(NomenclatureError::UnknownAnion(
+            anion_formula.to_string(),
+        )));
+    }
+
+    let mut name = match cation_element {
+        Some(element) if cation_ion.is_none() && MOLECULAR_ELEMENTS.contains(&element.symbol) => {
+            // Molecular compound, naming with greek prefixes.
+            let Some((anion, false, anion_count)) = anions.first() else {
+                return Err(Box::new(NomenclatureError::UnknownAnion(
+                    anion_formula.to_string(),
+                )));
+            };
+
+            let cation_prefix = if cation_count == 1 {
+                ""
+            } else {
+                greek_prefix(cation_count)?
+            };
+            let mut anion_prefix = greek_prefix(*anion_count)?;
+            // Eliding the last vowel of the prefix: monoxide, pentoxide.
+            if anion.name.starts_with('o') {
+                anion_prefix = anion_prefix
+                    .strip_suffix(['a', 'o'])
+                    .unwrap_or(anion_prefix);
+            }
+
+            format!(
+                "{cation_prefix}{} {anion_prefix}{}",
+                element.name, anion.name
+            )
+        }
+        _ => {
+            // Ionic compound, finding the cation oxidation state.
+            let (cation_name, positive_oxidation_states): (&str, Vec<i8>) =
+                match (cation_ion, cation_element) {
+                    (Some(ion), _) => (ion.name, vec![ion.charge]),
+                    (None, Some(element)) => {
+                        (element.name, element.positive_oxidation_states().collect())
+                    }
+                    (None, None) => {
+                        return Err(Box::new(NomenclatureError::UnknownCation(
+                            cation_formula.to_string(),
+                        )));
+                    }
+                };
+
+            // The first anion reading giving a usual oxidation state.
+            let Some((oxidation_state, anion_name)) =
+                anions.iter().find_map(|(anion, _, anion_count)| {
+                    let total_charge =
+                        usize::from(anion.charge.unsigned_abs()).checked_mul(*anion_count)?;
+                    if total_charge % cation_count != 0 {
+                        return None;
+                    }
+                    let oxidation_state = i8::try_from(total_charge / cation_count).ok()?;
+
+                    positive_oxidation_states
+                        .contains(&oxidation_state)
+                        .then_some((oxidation_state, anion.name))
+                })
+            else {
+                return Err(Box::new(NomenclatureError::ChargesCanNotBeBalanced(
+                    cation_formula.to_string(),
+                )));
+            };
+
+            if cation_ion.is_none() && positive_oxidation_states.len() > 1 {
+                format!(
+                    "{cation_name}({}) {anion_name}",
+                    to_roman(u16::from(oxidation_state.unsigned_abs()))
+                )
+            } else {
+                format!("{cation_name} {anion_name}")
+            }
+        }
+    };
+
+    if let Some(hydrate_name) = hydrate_name {
+        name.push(' ');
+        name.push_str(&hydrate_name);
+    }
+
+    debug!("name: {name}");
+
+    Ok(name)
+}
+
+/// Parses a cation name such as sodium, iron(III), ammonium or dinitrogen.
+fn parse_cation_name(word: &str) -> Result<NamedCation, Box<dyn Error + Send + Sync>> {
+    // Stock notation: iron(iii), iron(3+).
+    let (base_name, oxidation_state) = match word.split_once('(') {
+        Some((base_name, stock)) => {
+            let stock = stock.trim_end_matches(')');
+            let maybe_oxidation_state = match from_roman(stock) {
+                Some(oxidation_state) => i8::try_from(oxidation_state).ok(),
+                None => stock.trim_matches('+').parse::<i8>().ok(),
+            };
+            let Some(oxidation_state) =
+                maybe_oxidation_state.filter(|state| (1..=MAX_OXIDATION_STATE).contains(state))
+            else {
+                return Err(Box::new(NomenclatureError::InvalidOxidationState(
+                    stock.to_string(),
+                )));
+            };
+            (base_name, Some(oxidation_state))
+        }
+        None => (word, None),
+    };
+
+    if let Some(ion) = polyatomic_ion_by_name(base_name).filter(|ion| ion.charge > 0) {
+        return Ok(NamedCation {
+            formula: ion.formula,
+            is_polyatomic: true,
+            element: None,
+            oxidation_state: Some(ion.charge),
+            prefix_count: None,
+        });
+    }
+
+    if let Some(element) = element_by_name(base_name) {
+        return Ok(NamedCation {
+            formula: element.symbol,
+            is_polyatomic: false,
+            element: Some(element),
+            oxidation_state,
+            prefix_count: None,
+        });
+    }
+
+    for (prefix, count) in GREEK_PREFIXES {
+        if let Some(rest) = base_name.strip_prefix(prefix)
+            && let Some(element) = element_by_name(rest)
+        {
+            return Ok(NamedCation {
+                formula: element.symbol,
+                is_polyatomic: false,
+                element: Some(element),
+                oxidation_state,
+                prefix_count: Some(count),
+            });
+        }
+    }
+
+    Err(Box::new(NomenclatureError::UnknownCation(word.to_string())))
+}
+
+/// Parses an anion name such as chloride, hydrogen carbonate or pentoxide.
+fn parse_anion_name(words: &str) -> Result<NamedAnion, Box<dyn Error + Send + Sync>> {
+    if let Some(ion) = polyatomic_ion_by_name(words).filter(|ion| ion.charge < 0) {
+        return Ok(NamedAnion {
+            ion,
+            is_polyatomic: true,
+            prefix_count: None,
+        });
+    }
+
+    if let Some(ion) = monatomic_anion_by_name(words) {
+        return Ok(NamedAnion {
+            ion,
+            is_polyatomic: false,
+            prefix_count: None,
+        });
+    }
+
+    for (prefix, count) in GREEK_PREFIXES {
+        // Full prefix: trichloride, or elided prefix: pentoxide.
+        let elided_prefix = &prefix[..prefix.len() - 1];
+        let maybe_ion = words
+            .strip_prefix(prefix)
+            .and_then(monatomic_anion_by_name)
+            .or_else(|| {
+                words
+                    .strip_prefix(elided_prefix)
+                    .filter(|rest| rest.starts_with('o'))
+                    .and_then(monatomic_anion_by_name)
+            });
+
+        if let Some(ion) = maybe_ion {
+            return Ok(NamedAnion {
+                ion,
+                is_polyatomic: false,
+                prefix_count: Some(count),
+            });
+        }
+    }
+
+    Err(Box::new(NomenclatureError::UnknownAnion(words.to_string())))
+}
+
+/// Splits a formula into its cation, the cation count and the anion part.
+fn split_cation(formula: &str) -> Result<(&str, usize, &str), Box<dyn Error + Send + Sync>> {
+    // Polyatomic cation in parenthesis: (NH4)2SO4.
+    if let Some(group) = formula.strip_prefix('(')
+        && let Some((group, rest)) = group.split_once(')')
+        && polyatomic_ion_by_formula(group).is_some_and(|ion| ion.charge > 0)
+    {
+        let (count, rest) = parse_count(rest)?;
+        return Ok((group, count, rest));
+    }
+
+    // Polyatomic cation: NH4Cl.
+    for ion in POLYATOMIC_IONS.iter().filter(|ion| ion.charge > 0) {
+        if let Some(rest) = formula.strip_prefix(ion.formula) {
+            return Ok((ion.formula, 1, rest));
+        }
+    }
+
+    // Element.
+    let Some((element, rest)) = parse_symbol(formula) else {
+        return Err(Box::new(NomenclatureError::UnknownCation(
+            formula.to_string(),
+        )));
+    };
+    let (count, rest) = parse_count(rest)?;
+
+    if rest.is_empty() {
+        return Err(Box::new(NomenclatureError::UnknownAnion(
+            formula.to_string(),
+        )));
+    }
+
+    Ok((element.symbol, count, rest))
+}
+
+/// Parses the element symbol at the beginning of a formula.
+fn parse_symbol(formula: &str) -> Option<(&'static Element, &str)> {
+    let mut chars = formula.chars();
+    chars.next().filter(char::is_ascii_uppercase)?;
+
+    // Two chars symbol first.
+    if chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && let Some(element) = element_by_symbol(&formula[..2])
+    {
+        return Some((element, &formula[2..]));
+    }
+
+    element_by_symbol(&formula[..1]).map(|element| (element, &formula[1..]))
+}
+
+/// Parses the count at the beginning of a formula, 1 if there is no count.
+/// A zero count is an error.
+fn parse_count(formula: &str) -> Result<(usize, &str), Box<dyn Error + Send + Sync>> {
+    let digits_length = formula
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(formula.len());
+
+    match formula[..digits_length].parse::<usize>() {
+        Ok(0) => Err(Box::new(NomenclatureError::InvalidCount(
+            formula.to_string(),
+        ))),
+        Ok(count) => Ok((count, &formula[digits_length..])),
+        Err(_) => Ok((1, formula)),
+    }
+}
+
+/// Returns the greek prefix of a count.
+fn greek_prefix(count: usize) -> Result<&'static str, Box<dyn Error + Send + Sync>> {
+    match GREEK_PREFIXES.iter().find(|(_, c)| *c == count) {
+        Some((prefix, _)) => Ok(prefix),
+        None => Err(Box::new(NomenclatureError::NoGreekPrefix(count))),
+    }
+}
+
+/// Formats an ion with its count, adding parenthesis around polyatomic ions.
+fn format_formula_part(formula: &str, is_polyatomic: bool, count: usize) -> String {
+    match (count, is_polyatomic) {
+        (1, _) => formula.to_string(),
+        (_, true) => format!("({formula}){count}"),
+        (_, false) => format!("{formula}{count}"),
+    }
+}
+
+#[cfg(test)]
+#[path = "nomenclature_tests.rs"]
+mod nomenclature_tests;
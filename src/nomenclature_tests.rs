@@ -0,0 +1,302 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::nomenclature::*;
+
+    fn init_logger() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    #[test]
+    fn test_name_to_formula() {
+        init_logger();
+
+        let names_formulas = vec![
+            ("sodium chloride", "NaCl"),
+            ("Sodium  Chloride", "NaCl"),
+            ("potassium bromide", "KBr"),
+            ("calcium chloride", "CaCl2"),
+            ("aluminium oxide", "Al2O3"),
+            ("aluminum oxide", "Al2O3"),
+            ("magnesium nitride", "Mg3N2"),
+            ("iron(III) sulfate", "Fe2(SO4)3"),
+            ("iron (III) sulfate", "Fe2(SO4)3"),
+            ("iron(3+) sulfate", "Fe2(SO4)3"),
+            ("iron(II) chloride", "FeCl2"),
+            ("copper(I) oxide", "Cu2O"),
+            ("manganese(IV) oxide", "MnO2"),
+            ("ammonium sulfate", "(NH4)2SO4"),
+            ("ammonium chloride", "NH4Cl"),
+            ("calcium hydroxide", "Ca(OH)2"),
+            ("sodium hydrogen carbonate", "NaHCO3"),
+            ("sodium bicarbonate", "NaHCO3"),
+            ("potassium permanganate", "KMnO4"),
+            ("potassium dichromate", "K2Cr2O7"),
+            ("calcium phosphate", "Ca3(PO4)2"),
+            ("sodium sulphate", "Na2SO4"),
+            ("sodium peroxide", "Na2O2"),
+            ("hydrogen chloride", "HCl"),
+            ("hydrogen sulfide", "H2S"),
+            ("carbon dioxide", "CO2"),
+            ("carbon monoxide", "CO"),
+            ("dinitrogen pentoxide", "N2O5"),
+            ("dinitrogen tetroxide", "N2O4"),
+            ("sulfur hexafluoride", "SF6"),
+            ("phosphorus pentachloride", "PCl5"),
+            ("iron trichloride", "FeCl3"),
+            ("water", "H2O"),
+            ("copper(II) sulfate pentahydrate", "CuSO4·5H2O"),
+            ("magnesium sulfate heptahydrate", "MgSO4·7H2O"),
+            ("sodium carbonate monohydrate", "Na2CO3·H2O"),
+            ("anhydrous copper(II) sulfate", "CuSO4"),
+        ];
+
+        for (name, formula) in names_formulas {
+            assert_eq!(name_to_formula(name).unwrap(), formula, "-> error {name}");
+        }
+    }
+
+    #[test]
+    fn test_name_to_formula_errors() {
+        init_logger();
+
+        assert_eq!(
+            *name_to_formula("  ")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::EmptyName
+        );
+        assert_eq!(
+            *name_to_formula("sodium")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::IncompleteName("sodium".to_string())
+        );
+        assert_eq!(
+            *name_to_formula("iron sulfate")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::MissingOxidationState("iron".to_string())
+        );
+        assert_eq!(
+            *name_to_formula("unobtainium chloride")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::UnknownCation("unobtainium".to_string())
+        );
+        assert_eq!(
+            *name_to_formula("sodium chlorate pentakis")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::UnknownAnion("chlorate pentakis".to_string())
+        );
+        assert_eq!(
+            *name_to_formula("iron(XYZ) chloride")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::InvalidOxidationState("xyz".to_string())
+        );
+        assert_eq!(
+            *name_to_formula("iron(XL) oxide")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::InvalidOxidationState("xl".to_string())
+        );
+        assert!(name_to_formula(&format!("iron({}) chloride", "M".repeat(70))).is_err());
+        assert_eq!(
+            *name_to_formula("copper sulfate superhydrate")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::UnknownHydrate("superhydrate".to_string())
+        );
+    }
+
+    #[test]
+    fn test_formula_to_name() {
+        init_logger();
+
+        let formulas_names = vec![
+            ("NaCl", "sodium chloride"),
+            ("CaCl2", "calcium chloride"),
+            ("Al2O3", "aluminium oxide"),
+            ("Fe2(SO4)3", "iron(III) sulfate"),
+            ("FeCl2", "iron(II) chloride"),
+            ("FeCl3", "iron(III) chloride"),
+            ("Cu2O", "copper(I) oxide"),
+            ("MnO2", "manganese(IV) oxide"),
+            ("Na2O2", "sodium peroxide"),
+            ("BaO2", "barium peroxide"),
+            ("(NH4)2SO4", "ammonium sulfate"),
+            ("NH4Cl", "ammonium chloride"),
+            ("Ca(OH)2", "calcium hydroxide"),
+            ("NaHCO3", "sodium hydrogen carbonate"),
+            ("KMnO4", "potassium permanganate"),
+            ("Ca3(PO4)2", "calcium phosphate"),
+            ("HCl", "hydrogen chloride"),
+            ("CO2", "carbon dioxide"),
+            ("CO", "carbon monoxide"),
+            ("N2O5", "dinitrogen pentoxide"),
+            ("SF6", "sulfur hexafluoride"),
+            ("H2O", "water"),
+            ("NH3", "ammonia"),
+            ("CuSO4·5H2O", "copper(II) sulfate pentahydrate"),
+            ("CuSO4.5H2O", "copper(II) sulfate pentahydrate"),
+            ("Na2CO3 · H2O", "sodium carbonate monohydrate"),
+        ];
+
+        for (formula, name) in formulas_names {
+            assert_eq!(
+                formula_to_name(formula).unwrap(),
+                name,
+                "-> error {formula}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_formula_to_name_round_trip() {
+        init_logger();
+
+        let formulas = vec![
+            "NaCl",
+            "Fe2(SO4)3",
+            "(NH4)2SO4",
+            "K2Cr2O7",
+            "Mg3N2",
+            "N2O4",
+            "PCl5",
+            "CuSO4·5H2O",
+        ];
+
+        for formula in formulas {
+            let name = formula_to_name(formula).unwrap();
+            assert_eq!(name_to_formula(&name).unwrap(), formula, "-> error {name}");
+        }
+    }
+
+    #[test]
+    fn test_formula_to_name_errors() {
+        init_logger();
+
+        assert_eq!(
+            *formula_to_name("")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::EmptyFormula
+        );
+        assert_eq!(
+            *formula_to_name("Na")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::UnknownAnion("Na".to_string())
+        );
+        assert_eq!(
+            *formula_to_name("NaXy")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::UnknownAnion("Xy".to_string())
+        );
+        assert_eq!(
+            *formula_to_name("xyz")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::UnknownCation("xyz".to_string())
+        );
+        assert_eq!(
+            *formula_to_name("Fe3O4")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::ChargesCanNotBeBalanced("Fe".to_string())
+        );
+        // Unusual oxidation states are not guessed.
+        assert_eq!(
+            *formula_to_name("NaCl2")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::ChargesCanNotBeBalanced("Na".to_string())
+        );
+        assert_eq!(
+            *formula_to_name("Na0Cl")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::InvalidCount("0Cl".to_string())
+        );
+        assert_eq!(
+            *formula_to_name("(NH4)0SO4")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::InvalidCount("0SO4".to_string())
+        );
+        assert_eq!(
+            *formula_to_name("CuSO4·5NH3")
+                .unwrap_err()
+                .downcast::<NomenclatureError>()
+                .unwrap(),
+            NomenclatureError::UnknownHydrate("5NH3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_format_nomenclature_errors() {
+        assert_eq!(NomenclatureError::EmptyName.to_string(), "empty name");
+        assert_eq!(NomenclatureError::EmptyFormula.to_string(), "empty formula");
+        assert_eq!(
+            NomenclatureError::IncompleteName("sodium".to_string()).to_string(),
+            "incomplete name sodium"
+        );
+        assert_eq!(
+            NomenclatureError::UnknownCation("Xy".to_string()).to_string(),
+            "unknown cation Xy"
+        );
+        assert_eq!(
+            NomenclatureError::UnknownAnion("Xy".to_string()).to_string(),
+            "unknown anion Xy"
+        );
+        assert_eq!(
+            NomenclatureError::UnknownHydrate("Xy".to_string()).to_string(),
+            "unknown hydrate Xy"
+        );
+        assert_eq!(
+            NomenclatureError::MissingOxidationState("iron".to_string()).to_string(),
+            "missing oxidation state for iron"
+        );
+        assert_eq!(
+            NomenclatureError::InvalidOxidationState("xyz".to_string()).to_string(),
+            "invalid oxidation state xyz"
+        );
+        assert_eq!(
+            NomenclatureError::ChargesCanNotBeBalanced("Fe".to_string()).to_string(),
+            "charges can not be balanced for Fe"
+        );
+        assert_eq!(
+            NomenclatureError::NoGreekPrefix(42).to_string(),
+            "no greek prefix for 42"
+        );
+        assert_eq!(
+            NomenclatureError::InvalidCount("0Cl".to_string()).to_string(),
+            "invalid count 0Cl"
+        );
+    }
+}
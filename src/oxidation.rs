@@ -0,0 +1,621 @@
+use log::debug;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+use crate::{
+    element::element_by_symbol,
+    formula::{ToEmpiricalFormulaError, split_formula_charge},
+    ion::{Ion, POLYATOMIC_IONS, polyatomic_ion_by_formula},
+    lexer::ADDUCT_SEPARATORS,
+    string::to_roman,
+};
+
+/// Maximum number of ion segmentations explored for a formula part.
+const MAX_ION_SEGMENTATIONS: usize = 64;
+
+/// Maximum number of charge assignments returned.
+const MAX_CHARGE_ASSIGNMENTS: usize = 64;
+
+/// Maximum number of atoms of an element split between two oxidation states, as in `Fe3O4`.
+const MAX_MIXED_VALENCE_COUNT: usize = 24;
+
+/// Maximum number of nodes explored by the search of the oxidation states of a formula part.
+const MAX_BALANCE_NODES: usize = 100_000;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OxidationError {
+    TooManyCombinations(String),
+}
+
+impl Display for OxidationError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            OxidationError::TooManyCombinations(s) => {
+                write!(f, "too many oxidation state combinations for {s}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OxidationError {}
+
+/// An element or a polyatomic ion with its charge in a charge assignment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssignedSpecies {
+    /// The element symbol or the ion formula.
+    pub formula: String,
+    pub count: usize,
+    /// The oxidation state of the element or the charge of the ion.
+    pub charge: i8,
+    pub is_ion: bool,
+}
+
+impl Display for AssignedSpecies {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if self.is_ion {
+            if self.count == 1 {
+                write!(f, "{}", self.formula)
+            } else {
+                write!(f, "({}){}", self.formula, self.count)
+            }
+        } else {
+            let state = match self.charge {
+                0 => "0".to_string(),
+                charge if charge < 0 => format!("-{}", to_roman(charge.unsigned_abs().into())),
+                charge => to_roman(charge.unsigned_abs().into()),
+            };
+
+            write!(f, "{}({state})", self.formula)?;
+
+            if self.count != 1 {
+                write!(f, "{}", self.count)?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// The assigned species of one part of an adduct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssignedPart {
+    /// The leading coefficient of the part, as 5 in `·5H2O`.
+    pub coefficient: usize,
+    pub species: Vec<AssignedSpecies>,
+}
+
+/// A charge assignment balancing a formula.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChargeAssignment {
+    pub parts: Vec<AssignedPart>,
+}
+
+impl Display for ChargeAssignment {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        for (index, part) in self.parts.iter().enumerate() {
+            if index > 0 {
+                write!(f, "·")?;
+            }
+            if part.coefficient != 1 {
+                write!(f, "{}", part.coefficient)?;
+            }
+            for species in &part.species {
+                write!(f, "{species}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The counts of an element in each of its oxidation states, as `[(2, 1), (3, 2)]` for `Fe3`.
+type StateSplit = Vec<(i8, usize)>;
+
+/// The sorting key of an assignment: mixed valences, ions of a single element,
+/// other polyatomic ions and generation order.
+type AssignmentKey = (usize, usize, isize, usize);
+
+/// A parsed unit of a formula part.
+#[derive(Debug, Clone, Copy)]
+enum Unit {
+    Atom(&'static str, usize),
+    Ion(&'static Ion, usize),
+}
+
+/// Returns the charge assignments balancing a formula, with the common oxidation states
+/// of the elements and the common polyatomic ions.
+/// The charge of the formula, as in `NH4+`, is taken into account.
+/// Each part of an adduct, as `5H2O` in `CuSO4·5H2O`, is balanced on its own.
+/// Assignments without mixed valence and with polyatomic ions come first, but the ions of a single
+/// element, as the peroxide in `Na2O2`, come after the readings with atoms only: `CO2` is `C(IV)O(-II)2`.
+/// An empty result means that the formula can not be balanced, as `NaCl2` or `CaCl`, or that
+/// it is organic: carbon and hydrogen outside of the polyatomic ions, as `C2H6O`.
+/// Formulas needing too long a search, as `Cr20Mn20V20Fe20Co20`, return an error.
+pub fn charge_assignments(
+    formula: &str,
+) -> Result<Vec<ChargeAssignment>, Box<dyn Error + Send + Sync>> {
+    let (formula, charge) = split_formula_charge(formula);
+
+    if formula.trim().is_empty() {
+        return Err(Box::new(ToEmpiricalFormulaError::EmptyFormula));
+    }
+
+    let mut assignments: Vec<ChargeAssignment> = vec![ChargeAssignment { parts: Vec::new() }];
+
+    for (index, part) in formula.split(ADDUCT_SEPARATORS).enumerate() {
+        let part = part.trim();
+
+        // Leading coefficient.
+        let formula_index = part
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(part.len());
+        let (coefficient_string, part) = part.split_at(formula_index);
+        let coefficient = if coefficient_string.is_empty() {
+            1
+        } else {
+            coefficient_string
+                .parse::<usize>()
+                .map_err(ToEmpiricalFormulaError::CanNotParseNumber)?
+        };
+
+        if part.is_empty() {
+            return Err(Box::new(ToEmpiricalFormulaError::EmptyFormula));
+        }
+
+        // The charge of the formula is carried by the first part.
+        let part_charge = if index == 0 { charge } else { 0 };
+        let part_assignments = part_charge_assignments(part, part_charge)?;
+
+        debug!("part: {part} assignments: {part_assignments:?}");
+
+        // Combining with the previous parts.
+        assignments = assignments
+            .iter()
+            .flat_map(|assignment| {
+                part_assignments.iter().map(|species| {
+                    let mut parts = assignment.parts.clone();
+                    parts.push(AssignedPart {
+                        coefficient,
+                        species: species.clone(),
+                    });
+
+                    ChargeAssignment { parts }
+                })
+            })
+            .take(MAX_CHARGE_ASSIGNMENTS)
+            .collect();
+    }
+
+    Ok(assignments)
+}
+
+/// Returns true if the formula can be charge balanced.
+/// See `charge_assignments`.
+pub fn is_charge_balanced(formula: &str) -> Result<bool, Box<dyn Error + Send + Sync>> {
+    Ok(!charge_assignments(formula)?.is_empty())
+}
+
+/// Returns the charge assignments of a formula part without adduct.
+fn part_charge_assignments(
+    part: &str,
+    charge: isize,
+) -> Result<Vec<Vec<AssignedSpecies>>, Box<dyn Error + Send + Sync>> {
+    let units = parse_units(part)?;
+
+    // The charges summed while balancing are bounded by this one, it must not overflow.
+    if max_charge(&units).is_none() {
+        return Err(Box::new(ToEmpiricalFormulaError::CountOverflow));
+    }
+
+    let mut keyed_assignments: Vec<(AssignmentKey, Vec<AssignedSpecies>)> = Vec::new();
+    let mut node_budget = MAX_BALANCE_NODES;
+
+    for segmentation in segment_ions(&units) {
+        let Some(balanced_units) = balance_units(&segmentation, charge, &mut node_budget) else {
+            return Err(Box::new(OxidationError::TooManyCombinations(
+                part.to_string(),
+            )));
+        };
+
+        for species in balanced_units {
+            let mut elements: Vec<&str> = species
+                .iter()
+                .filter(|species| !species.is_ion)
+                .map(|species| species.formula.as_str())
+                .collect();
+            let element_count = elements.len();
+            elements.dedup();
+            let mixed_valences = element_count - elements.len();
+
+            let (homoatomic_ions, ions): (Vec<&AssignedSpecies>, Vec<&AssignedSpecies>) = species
+                .iter()
+                .filter(|species| species.is_ion)
+                .partition(|species| is_homoatomic_ion(&species.formula));
+            let key = (
+                mixed_valences,
+                homoatomic_ions.len(),
+                -isize::try_from(ions.len()).unwrap_or(isize::MAX),
+                keyed_assignments.len(),
+            );
+
+            if !keyed_assignments
+                .iter()
+                .any(|(_, assignment)| *assignment == species)
+            {
+                keyed_assignments.push((key, species));
+            }
+        }
+    }
+
+    keyed_assignments.sort_by_key(|(key, _)| *key);
+
+    Ok(keyed_assignments
+        .into_iter()
+        .map(|(_, species)| species)
+        .take(MAX_CHARGE_ASSIGNMENTS)
+        .collect())
+}
+
+/// Parses a formula part into atoms and polyatomic ions.
+/// Bracket groups matching a polyatomic ion, as `(SO4)3`, are kept as ions,
+/// other groups are expanded into their atoms.
+fn parse_units(part: &str) -> Result<Vec<Unit>, Box<dyn Error + Send + Sync>> {
+    let chars: Vec<char> = part.chars().collect();
+    let mut units: Vec<Unit> = Vec::new();
+    let mut index = 0;
+
+    // The count after an atom or a group, 1 if there is none.
+    let read_count = |index: &mut usize| -> Result<usize, ToEmpiricalFormulaError> {
+        let start = *index;
+        while *index < chars.len() && chars[*index].is_ascii_digit() {
+            *index += 1;
+        }
+        if start == *index {
+            return Ok(1);
+        }
+        chars[start..*index]
+            .iter()
+            .collect::<String>()
+            .parse::<usize>()
+            .map_err(ToEmpiricalFormulaError::CanNotParseNumber)
+    };
+
+    while index < chars.len() {
+        match chars[index] {
+            '(' | '[' => {
+                // Finding the matching closing bracket.
+                let mut depth = 0;
+                let mut closing_index = None;
+                for (offset, c) in chars[index..].iter().enumerate() {
+                    match c {
+                        '(' | '[' => depth += 1,
+                        ')' | ']' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                closing_index = Some(index + offset);
+                                break;
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+
+                let Some(closing_index) = closing_index else {
+                    return Err(Box::new(ToEmpiricalFormulaError::UnbalancedParenthesis));
+                };
+
+                let content: String = chars[index + 1..closing_index].iter().collect();
+                index = closing_index + 1;
+                let count = read_count(&mut index)?;
+
+                if let Some(ion) = polyatomic_ion_by_formula(&content) {
+                    units.push(Unit::Ion(ion, count));
+                } else {
+                    for unit in parse_units(&content)? {
+                        units.push(match unit {
+                            Unit::Atom(symbol, atom_count) => Unit::Atom(
+                                symbol,
+                                atom_count
+                                    .checked_mul(count)
+                                    .ok_or(ToEmpiricalFormulaError::CountOverflow)?,
+                            ),
+                            Unit::Ion(ion, ion_count) => Unit::Ion(
+                                ion,
+                                ion_count
+                                    .checked_mul(count)
+                                    .ok_or(ToEmpiricalFormulaError::CountOverflow)?,
+                            ),
+                        });
+                    }
+                }
+            }
+            ')' | ']' => return Err(Box::new(ToEmpiricalFormulaError::UnbalancedParenthesis)),
+            'A'..='Z' => {
+                let mut symbol = chars[index].to_string();
+                if let Some(next_char) = chars.get(index + 1)
+                    && next_char.is_ascii_lowercase()
+                {
+                    symbol.push(*next_char);
+                }
+
+                let Some(element) = element_by_symbol(&symbol) else {
+                    return Err(Box::new(ToEmpiricalFormulaError::UnknowAtom(symbol)));
+                };
+
+                index += symbol.len();
+                let count = read_count(&mut index)?;
+
+                units.push(Unit::Atom(element.symbol, count));
+            }
+            _ => index += 1,
+        }
+    }
+
+    Ok(units)
+}
+
+/// Returns the highest absolute charge the units can carry, `None` if it overflows.
+fn max_charge(units: &[Unit]) -> Option<isize> {
+    units.iter().try_fold(0_isize, |total, unit| {
+        let (count, charge) = match unit {
+            Unit::Atom(symbol, count) => (
+                *count,
+                element_by_symbol(symbol)
+                    .and_then(|element| element.oxidation_states.iter().map(|s| s.abs()).max())
+                    .unwrap_or(0),
+            ),
+            Unit::Ion(ion, count) => (*count, ion.charge.abs()),
+        };
+
+        isize::try_from(count)
+            .ok()?
+            .checked_mul(isize::from(charge.max(1)))?
+            .checked_add(total)
+    })
+}
+
+/// Returns true if the ion is made of a single element, as the peroxide `O2`.
+fn is_homoatomic_ion(formula: &str) -> bool {
+    element_by_symbol(formula.trim_end_matches(|c: char| c.is_ascii_digit())).is_some()
+}
+
+/// Returns the possible readings of the units with polyatomic ions written without brackets,
+/// as `NO3` in `NaNO3`. The reading without any ion is always returned.
+fn segment_ions(units: &[Unit]) -> Vec<Vec<Unit>> {
+    // The atoms of each polyatomic ion.
+    let ion_units: Vec<(&'static Ion, Vec<Unit>)> = POLYATOMIC_IONS
+        .iter()
+        .filter_map(|ion| parse_units(ion.formula).ok().map(|units| (ion, units)))
+        .collect();
+
+    let mut segmentations: Vec<Vec<Unit>> = Vec::new();
+    let mut stack: Vec<(usize, Vec<Unit>)> = vec![(0, Vec::new())];
+
+    while let Some((index, segmentation)) = stack.pop() {
+        if segmentations.len() >= MAX_ION_SEGMENTATIONS {
+            break;
+        }
+
+        if index == units.len() {
+            segmentations.push(segmentation);
+            continue;
+        }
+
+        // Reading the unit as is.
+        let mut next_segmentation = segmentation.clone();
+        next_segmentation.push(units[index]);
+        stack.push((index + 1, next_segmentation));
+
+        // Reading a polyatomic ion.
+        for (ion, atoms) in &ion_units {
+            let end = index + atoms.len();
+            if end > units.len() {
+                continue;
+            }
+
+            let is_matching =
+                units[index..end]
+                    .iter()
+                    .zip(atoms)
+                    .all(|(unit, atom)| match (unit, atom) {
+                        (Unit::Atom(symbol, count), Unit::Atom(ion_symbol, ion_count)) => {
+                            symbol == ion_symbol && count == ion_count
+                        }
+                        _ => false,
+                    });
+
+            if is_matching {
+                let mut next_segmentation = segmentation.clone();
+                next_segmentation.push(Unit::Ion(ion, 1));
+                stack.push((end, next_segmentation));
+            }
+        }
+    }
+
+    segmentations
+}
+
+/// Returns the oxidation states of the free atoms balancing the units.
+/// Units with free carbon and hydrogen atoms are organic and are not balanced.
+/// Returns `None` when the node budget, shared by the calls, is exhausted.
+fn balance_units(
+    units: &[Unit],
+    charge: isize,
+    node_budget: &mut usize,
+) -> Option<Vec<Vec<AssignedSpecies>>> {
+    // Fixed charge of the ions, and free atoms merged by element in order of appearance.
+    let mut ion_charge: isize = 0;
+    let mut elements: Vec<(&'static str, usize)> = Vec::new();
+
+    for unit in units {
+        match unit {
+            Unit::Ion(ion, count) => {
+                ion_charge += isize::from(ion.charge) * isize::try_from(*count).unwrap_or(0);
+            }
+            Unit::Atom(symbol, count) => {
+                if let Some(element) = elements.iter_mut().find(|(s, _)| s == symbol) {
+                    element.1 += count;
+                } else {
+                    elements.push((symbol, *count));
+                }
+            }
+        }
+    }
+
+    let has_ions = units.iter().any(|unit| matches!(unit, Unit::Ion(..)));
+
+    let has_element =
+        |symbols: &[&str]| elements.iter().any(|(symbol, _)| symbols.contains(symbol));
+    if has_element(&["C"]) && has_element(&["H", "D"]) {
+        debug!("organic units: {units:?}");
+        return Some(Vec::new());
+    }
+
+    // The possible (state, count) splits of each element, with their charge.
+    let element_options: Vec<Vec<(StateSplit, isize)>> = elements
+        .iter()
+        .map(|(symbol, count)| {
+            let states: &[i8] = if elements.len() == 1 && !has_ions {
+                // An elemental substance.
+                &[0]
+            } else {
+                element_by_symbol(symbol).map_or(&[], |element| element.oxidation_states)
+            };
+
+            let mut options: Vec<StateSplit> =
+                states.iter().map(|state| vec![(*state, *count)]).collect();
+
+            if *count > 1 && *count <= MAX_MIXED_VALENCE_COUNT {
+                for (index, low_state) in states.iter().enumerate() {
+                    for high_state in &states[index + 1..] {
+                        for low_count in 1..*count {
+                            options.push(vec![
+                                (*low_state, low_count),
+                                (*high_state, count - low_count),
+                            ]);
+                        }
+                    }
+                }
+            }
+
+            options
+                .into_iter()
+                .map(|option| {
+                    let option_charge = option
+                        .iter()
+                        .map(|(state, count)| {
+                            isize::from(*state) * isize::try_from(*count).unwrap_or(0)
+                        })
+                        .sum();
+                    (option, option_charge)
+                })
+                .collect()
+        })
+        .collect();
+
+    // The lowest and highest charges the elements from an index can still bring.
+    let mut charge_bounds: Vec<(isize, isize)> = vec![(0, 0); element_options.len() + 1];
+    for (index, options) in element_options.iter().enumerate().rev() {
+        let charges = options.iter().map(|(_, option_charge)| *option_charge);
+        let (min_rest, max_rest) = charge_bounds[index + 1];
+        charge_bounds[index] = (
+            min_rest + charges.clone().min().unwrap_or(0),
+            max_rest + charges.max().unwrap_or(0),
+        );
+    }
+
+    // Depth first search of the options, pruning the branches that can not reach the charge.
+    let mut results: Vec<Vec<AssignedSpecies>> = Vec::new();
+    let mut stack: Vec<(usize, isize, Vec<&StateSplit>)> = vec![(0, ion_charge, Vec::new())];
+
+    while let Some((index, total_charge, chosen)) = stack.pop() {
+        if results.len() >= MAX_CHARGE_ASSIGNMENTS {
+            break;
+        }
+
+        *node_budget = node_budget.checked_sub(1)?;
+
+        let (min_rest, max_rest) = charge_bounds[index];
+        if total_charge + min_rest > charge || total_charge + max_rest < charge {
+            continue;
+        }
+
+        if index == element_options.len() {
+            results.push(assigned_species(units, &elements, &chosen));
+            continue;
+        }
+
+        // Pushing in reverse to explore the options in order.
+        for (option, option_charge) in element_options[index].iter().rev() {
+            let mut next_chosen = chosen.clone();
+            next_chosen.push(option);
+            stack.push((index + 1, total_charge + option_charge, next_chosen));
+        }
+    }
+
+    Some(results)
+}
+
+/// Builds the assigned species in order of appearance.
+fn assigned_species(
+    units: &[Unit],
+    elements: &[(&'static str, usize)],
+    chosen: &[&StateSplit],
+) -> Vec<AssignedSpecies> {
+    let element_states: HashMap<&str, &StateSplit> = elements
+        .iter()
+        .zip(chosen)
+        .map(|((symbol, _), option)| (*symbol, *option))
+        .collect();
+
+    let mut species: Vec<AssignedSpecies> = Vec::new();
+
+    for unit in units {
+        match unit {
+            Unit::Ion(ion, count) => {
+                if let Some(last) = species.last_mut()
+                    && last.is_ion
+                    && last.formula == ion.formula
+                {
+                    last.count += count;
+                } else {
+                    species.push(AssignedSpecies {
+                        formula: ion.formula.to_string(),
+                        count: *count,
+                        charge: ion.charge,
+                        is_ion: true,
+                    });
+                }
+            }
+            Unit::Atom(symbol, _) => {
+                // Elements are written once, at their first appearance.
+                if species
+                    .iter()
+                    .any(|species| !species.is_ion && species.formula == *symbol)
+                {
+                    continue;
+                }
+
+                if let Some(states) = element_states.get(symbol) {
+                    for (state, count) in *states {
+                        species.push(AssignedSpecies {
+                            formula: (*symbol).to_string(),
+                            count: *count,
+                            charge: *state,
+                            is_ion: false,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    species
+}
+
+#[cfg(test)]
+#[path = "oxidation_tests.rs"]
+mod oxidation_tests;
@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::oxidation::*;
+
+    fn init_logger() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    fn first_assignment(formula: &str) -> String {
+        charge_assignments(formula).unwrap()[0].to_string()
+    }
+
+    #[test]
+    fn test_charge_assignments() {
+        init_logger();
+
+        assert_eq!(first_assignment("NaCl"), "Na(I)Cl(-I)");
+        assert_eq!(first_assignment("CaCl2"), "Ca(II)Cl(-I)2");
+        assert_eq!(first_assignment("Fe3O4"), "Fe(II)Fe(III)2O(-II)4");
+        assert_eq!(first_assignment("Pb3O4"), "Pb(II)2Pb(IV)O(-II)4");
+        assert_eq!(first_assignment("FeCl3"), "Fe(III)Cl(-I)3");
+        assert_eq!(first_assignment("Fe2(SO4)3"), "Fe(III)2(SO4)3");
+        assert_eq!(first_assignment("NaNO3"), "Na(I)NO3");
+        assert_eq!(first_assignment("NH4NO3"), "NH4NO3");
+        assert_eq!(first_assignment("KMnO4"), "K(I)MnO4");
+        assert_eq!(first_assignment("Ca(OH)2"), "Ca(II)(OH)2");
+        assert_eq!(first_assignment("K4[Fe(CN)6]"), "K(I)4Fe(II)(CN)6");
+        assert_eq!(first_assignment("CuSO4·5H2O"), "Cu(II)SO4·5H(I)2O(-II)");
+        assert_eq!(first_assignment("NH4+"), "NH4");
+        assert_eq!(first_assignment("SO4^2-"), "SO4");
+        assert_eq!(first_assignment("Fe"), "Fe(0)");
+        assert_eq!(first_assignment("O2"), "O(0)2");
+
+        // Ions of a single element come after the atoms.
+        assert_eq!(first_assignment("CO2"), "C(IV)O(-II)2");
+        assert_eq!(first_assignment("SiO2"), "Si(IV)O(-II)2");
+        assert_eq!(first_assignment("Na2O2"), "Na(I)2O2");
+        assert_eq!(first_assignment("BaO2"), "Ba(II)O2");
+
+        let assignments = charge_assignments("Fe3O4").unwrap();
+        assert_eq!(
+            assignments[0].parts[0].species[1],
+            AssignedSpecies {
+                formula: "Fe".to_string(),
+                count: 2,
+                charge: 3,
+                is_ion: false,
+            }
+        );
+
+        // Typos.
+        assert!(charge_assignments("NaCl2").unwrap().is_empty());
+        assert!(charge_assignments("CaCl").unwrap().is_empty());
+        assert!(charge_assignments("CuSO4·5H3O").unwrap().is_empty());
+
+        // Organic formulas.
+        assert!(charge_assignments("C2H6O").unwrap().is_empty());
+        assert!(charge_assignments("CH3CH2OH").unwrap().is_empty());
+        assert_eq!(first_assignment("CH3COONa"), "CH3COONa(I)");
+        assert_eq!(first_assignment("Na2CO3"), "Na(I)2CO3");
+
+        // Branches that can not reach the charge are pruned, long searches are cut.
+        assert!(
+            charge_assignments("Cr20Mn20V20Fe20Co20")
+                .unwrap()
+                .is_empty()
+        );
+        assert!(charge_assignments("Cr10Mn10V10Fe10").unwrap().is_empty());
+        assert_eq!(
+            charge_assignments("S20Se20Te20Po20Cl")
+                .unwrap_err()
+                .to_string(),
+            "too many oxidation state combinations for S20Se20Te20Po20Cl"
+        );
+
+        assert!(charge_assignments("").is_err());
+        assert!(charge_assignments("Xx").is_err());
+        assert!(charge_assignments("Ca(OH2").is_err());
+
+        // Counts overflowing.
+        assert_eq!(
+            charge_assignments("(Fe9999999999)9999999999")
+                .unwrap_err()
+                .to_string(),
+            "atom count overflow"
+        );
+        assert_eq!(
+            charge_assignments("Fe5000000000000000000O")
+                .unwrap_err()
+                .to_string(),
+            "atom count overflow"
+        );
+        assert_eq!(
+            charge_assignments("Fe99999999999999999999O")
+                .unwrap_err()
+                .to_string(),
+            "can not parse number: number too large to fit in target type"
+        );
+    }
+
+    #[test]
+    fn test_is_charge_balanced() {
+        init_logger();
+
+        assert!(is_charge_balanced("NaCl").unwrap());
+        assert!(is_charge_balanced("Al2O3").unwrap());
+        assert!(is_charge_balanced("Na2S2O3").unwrap());
+        assert!(!is_charge_balanced("NaCl2").unwrap());
+        assert!(!is_charge_balanced("CaCl").unwrap());
+    }
+}
@@ -0,0 +1,237 @@
+use log::debug;
+use std::fmt::{Display, Formatter};
+
+use crate::define::ALL_ZERO_RE;
+
+/// The registries whose identifiers are found in supplier catalogues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RegistryKind {
+    /// MDL number, as `MFCD00003568`.
+    Mdl,
+    /// `PubChem` compound identifier, as `2244`.
+    PubChemCid,
+    /// `PubChem` substance identifier, as `135626838`.
+    PubChemSid,
+    /// `ChEBI` identifier, as `CHEBI:15377`.
+    Chebi,
+    /// `ChEMBL` identifier, as `CHEMBL25`.
+    Chembl,
+    /// EPA `CompTox` substance identifier, as `DTXSID7020182`.
+    Dtxsid,
+    /// Wikidata item identifier, as `Q283`.
+    Wikidata,
+}
+
+/// The kinds tried by `parse_registry_identifier`, `Chembl` before `Chebi`.
+const REGISTRY_KINDS: [RegistryKind; 7] = [
+    RegistryKind::Mdl,
+    RegistryKind::PubChemCid,
+    RegistryKind::PubChemSid,
+    RegistryKind::Chembl,
+    RegistryKind::Chebi,
+    RegistryKind::Dtxsid,
+    RegistryKind::Wikidata,
+];
+
+impl RegistryKind {
+    /// Returns the prefix of the identifiers, uppercase.
+    #[must_use]
+    pub fn prefix(self) -> &'static str {
+        match self {
+            RegistryKind::Mdl => "MFCD",
+            RegistryKind::PubChemCid => "CID",
+            RegistryKind::PubChemSid => "SID",
+            RegistryKind::Chebi => "CHEBI",
+            RegistryKind::Chembl => "CHEMBL",
+            RegistryKind::Dtxsid => "DTXSID",
+            RegistryKind::Wikidata => "Q",
+        }
+    }
+
+    /// Returns true if the digits have the length and the leading digit of the kind.
+    fn is_valid_digits(self, digits: &str) -> bool {
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+
+        match self {
+            RegistryKind::Mdl => digits.len() == 8,
+            RegistryKind::Dtxsid => (7..=9).contains(&digits.len()),
+            _ => digits.len() <= 12 && !digits.starts_with('0'),
+        }
+    }
+}
+
+impl Display for RegistryKind {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            RegistryKind::Mdl => write!(f, "MDL number"),
+            RegistryKind::PubChemCid => write!(f, "PubChem CID"),
+            RegistryKind::PubChemSid => write!(f, "PubChem SID"),
+            RegistryKind::Chebi => write!(f, "ChEBI ID"),
+            RegistryKind::Chembl => write!(f, "ChEMBL ID"),
+            RegistryKind::Dtxsid => write!(f, "DTXSID"),
+            RegistryKind::Wikidata => write!(f, "Wikidata ID"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RegistryIdentifierError {
+    EmptyIdentifier,
+    InvalidFormat(RegistryKind, String),
+    AllZeros(RegistryKind),
+    UnknownIdentifier(String),
+}
+
+impl Display for RegistryIdentifierError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match &self {
+            RegistryIdentifierError::EmptyIdentifier => write!(f, "empty identifier"),
+            RegistryIdentifierError::InvalidFormat(kind, s) => {
+                write!(f, "invalid {kind} format {s}")
+            }
+            RegistryIdentifierError::AllZeros(kind) => write!(f, "{kind} is all zeros"),
+            RegistryIdentifierError::UnknownIdentifier(s) => write!(f, "unknown identifier {s}"),
+        }
+    }
+}
+
+impl std::error::Error for RegistryIdentifierError {}
+
+/// A registry identifier that went through `normalize_registry_identifier`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RegistryIdentifier {
+    kind: RegistryKind,
+    digits: String,
+}
+
+impl RegistryIdentifier {
+    #[must_use]
+    pub fn kind(&self) -> RegistryKind {
+        self.kind
+    }
+
+    /// Returns the digits of the identifier, without the prefix.
+    #[must_use]
+    pub fn digits(&self) -> &str {
+        &self.digits
+    }
+
+    /// Returns the URL of the identifier page, built without any request.
+    /// MDL numbers have no public registry, the URL is a `PubChem` search.
+    #[must_use]
+    pub fn url(&self) -> String {
+        match self.kind {
+            RegistryKind::Mdl => format!("https://pubchem.ncbi.nlm.nih.gov/#query={self}"),
+            RegistryKind::PubChemCid => {
+                format!("https://pubchem.ncbi.nlm.nih.gov/compound/{}", self.digits)
+            }
+            RegistryKind::PubChemSid => {
+                format!("https://pubchem.ncbi.nlm.nih.gov/substance/{}", self.digits)
+            }
+            RegistryKind::Chebi => {
+                format!("https://www.ebi.ac.uk/chebi/searchId.do?chebiId={self}")
+            }
+            RegistryKind::Chembl => {
+                format!("https://www.ebi.ac.uk/chembl/compound_report_card/{self}/")
+            }
+            RegistryKind::Dtxsid => {
+                format!("https://comptox.epa.gov/dashboard/chemical/details/{self}")
+            }
+            RegistryKind::Wikidata => format!("https://www.wikidata.org/wiki/{self}"),
+        }
+    }
+}
+
+impl Display for RegistryIdentifier {
+    /// Writes the identifier in its registry form: `MFCD00003568`, `2244` for `PubChem`,
+    /// `CHEBI:15377`, `CHEMBL25`, `DTXSID7020182`, `Q283`.
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self.kind {
+            RegistryKind::PubChemCid | RegistryKind::PubChemSid => write!(f, "{}", self.digits),
+            RegistryKind::Chebi => write!(f, "CHEBI:{}", self.digits),
+            kind => write!(f, "{}{}", kind.prefix(), self.digits),
+        }
+    }
+}
+
+/// Normalizes an identifier of the given kind. The prefix is optional and case insensitive,
+/// and may be followed by whitespaces, `:`, `-` or `_`: `chebi_15377`, `CHEBI 15377` and
+/// `15377` give `CHEBI:15377`.
+/// MDL numbers have 8 digits and DTXSIDs 7 to 9 digits, the other identifiers are positive
+/// numbers without leading zeros.
+pub fn normalize_registry_identifier(
+    kind: RegistryKind,
+    identifier: &str,
+) -> Result<RegistryIdentifier, Box<dyn std::error::Error + Send + Sync>> {
+    let trimmed_identifier = identifier.trim();
+
+    if trimmed_identifier.is_empty() {
+        return Err(Box::new(RegistryIdentifierError::EmptyIdentifier));
+    }
+
+    let digits = strip_prefix_ignore_case(trimmed_identifier, kind.prefix())
+        .map_or(trimmed_identifier, |rest| {
+            rest.trim_start_matches(|c: char| c.is_whitespace() || matches!(c, ':' | '-' | '_'))
+        });
+    debug!("kind:{kind} digits:{digits}");
+
+    if ALL_ZERO_RE.is_match(digits) {
+        return Err(Box::new(RegistryIdentifierError::AllZeros(kind)));
+    }
+
+    if !kind.is_valid_digits(digits) {
+        return Err(Box::new(RegistryIdentifierError::InvalidFormat(
+            kind,
+            identifier.to_string(),
+        )));
+    }
+
+    Ok(RegistryIdentifier {
+        kind,
+        digits: digits.to_string(),
+    })
+}
+
+/// Check if a string is a valid identifier of the given kind, see `normalize_registry_identifier`.
+pub fn is_registry_identifier(
+    kind: RegistryKind,
+    identifier: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    normalize_registry_identifier(kind, identifier).map(|_| ())
+}
+
+/// Normalizes an identifier of any kind, recognized by its prefix: `MFCD00003568`, `CID 2244`,
+/// `SID 135626838`, `CHEBI:15377`, `CHEMBL25`, `DTXSID7020182` or `Q283`.
+/// Bare numbers are rejected as their kind is unknown.
+pub fn parse_registry_identifier(
+    identifier: &str,
+) -> Result<RegistryIdentifier, Box<dyn std::error::Error + Send + Sync>> {
+    let trimmed_identifier = identifier.trim();
+
+    if trimmed_identifier.is_empty() {
+        return Err(Box::new(RegistryIdentifierError::EmptyIdentifier));
+    }
+
+    let Some(kind) = REGISTRY_KINDS
+        .into_iter()
+        .find(|kind| strip_prefix_ignore_case(trimmed_identifier, kind.prefix()).is_some())
+    else {
+        return Err(Box::new(RegistryIdentifierError::UnknownIdentifier(
+            identifier.to_string(),
+        )));
+    };
+
+    normalize_registry_identifier(kind, trimmed_identifier)
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let index = prefix.len();
+
+    (s.is_char_boundary(index) && s[..index].eq_ignore_ascii_case(prefix)).then(|| &s[index..])
+}
+
+#[cfg(test)]
+#[path = "registry_tests.rs"]
+mod registry_tests;
@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::registry::*;
+
+    fn init_logger() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    #[test]
+    fn test_normalize_registry_identifier() {
+        init_logger();
+
+        let identifiers = [
+            (RegistryKind::Mdl, "MFCD00003568", "MFCD00003568"),
+            (RegistryKind::Mdl, " mfcd00003568 ", "MFCD00003568"),
+            (RegistryKind::Mdl, "00003568", "MFCD00003568"),
+            (RegistryKind::PubChemCid, "2244", "2244"),
+            (RegistryKind::PubChemCid, "CID 2244", "2244"),
+            (RegistryKind::PubChemCid, "cid:2244", "2244"),
+            (RegistryKind::PubChemSid, "SID 135626838", "135626838"),
+            (RegistryKind::Chebi, "CHEBI:15377", "CHEBI:15377"),
+            (RegistryKind::Chebi, "chebi_15377", "CHEBI:15377"),
+            (RegistryKind::Chebi, "15377", "CHEBI:15377"),
+            (RegistryKind::Chembl, "CHEMBL25", "CHEMBL25"),
+            (RegistryKind::Chembl, "chembl25", "CHEMBL25"),
+            (RegistryKind::Dtxsid, "DTXSID7020182", "DTXSID7020182"),
+            (RegistryKind::Dtxsid, "dtxsid30873142", "DTXSID30873142"),
+            (RegistryKind::Wikidata, "Q283", "Q283"),
+            (RegistryKind::Wikidata, "q283", "Q283"),
+        ];
+        for (kind, identifier, expected) in identifiers {
+            let registry_identifier = normalize_registry_identifier(kind, identifier).unwrap();
+            assert_eq!(registry_identifier.kind(), kind);
+            assert_eq!(registry_identifier.to_string(), expected, "{identifier:?}");
+            assert!(is_registry_identifier(kind, identifier).is_ok());
+        }
+
+        assert_eq!(
+            normalize_registry_identifier(RegistryKind::Chebi, "chebi_15377")
+                .unwrap()
+                .digits(),
+            "15377"
+        );
+
+        assert_eq!(
+            normalize_registry_identifier(RegistryKind::Chebi, " ")
+                .unwrap_err()
+                .to_string(),
+            "empty identifier"
+        );
+        assert_eq!(
+            normalize_registry_identifier(RegistryKind::Mdl, "MFCD0003568")
+                .unwrap_err()
+                .to_string(),
+            "invalid MDL number format MFCD0003568"
+        );
+        assert_eq!(
+            normalize_registry_identifier(RegistryKind::Mdl, "MFCD00000000")
+                .unwrap_err()
+                .to_string(),
+            "MDL number is all zeros"
+        );
+        assert_eq!(
+            normalize_registry_identifier(RegistryKind::PubChemCid, "0")
+                .unwrap_err()
+                .to_string(),
+            "PubChem CID is all zeros"
+        );
+
+        let invalid_identifiers = [
+            (RegistryKind::Mdl, "MFCD000035681"),
+            (RegistryKind::Mdl, "MFCD0000356A"),
+            (RegistryKind::PubChemCid, "02244"),
+            (RegistryKind::PubChemCid, "-2244"),
+            (RegistryKind::PubChemCid, "SID 2244"),
+            (RegistryKind::Chebi, "CHEBI:"),
+            (RegistryKind::Chembl, "CHEMBL 25.1"),
+            (RegistryKind::Dtxsid, "DTXSID702018"),
+            (RegistryKind::Dtxsid, "DTXCID7020182"),
+            (RegistryKind::Wikidata, "P31"),
+            (RegistryKind::Wikidata, "Q"),
+        ];
+        for (kind, identifier) in invalid_identifiers {
+            assert!(
+                is_registry_identifier(kind, identifier).is_err(),
+                "{identifier:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_registry_identifier() {
+        init_logger();
+
+        let identifiers = [
+            ("MFCD00003568", RegistryKind::Mdl),
+            ("CID 2244", RegistryKind::PubChemCid),
+            ("SID:135626838", RegistryKind::PubChemSid),
+            ("CHEBI:15377", RegistryKind::Chebi),
+            ("CHEMBL25", RegistryKind::Chembl),
+            ("DTXSID7020182", RegistryKind::Dtxsid),
+            ("Q283", RegistryKind::Wikidata),
+        ];
+        for (identifier, kind) in identifiers {
+            assert_eq!(parse_registry_identifier(identifier).unwrap().kind(), kind);
+        }
+
+        assert_eq!(
+            parse_registry_identifier("2244").unwrap_err().to_string(),
+            "unknown identifier 2244"
+        );
+        assert_eq!(
+            parse_registry_identifier("CHEBI:abc")
+                .unwrap_err()
+                .to_string(),
+            "invalid ChEBI ID format CHEBI:abc"
+        );
+    }
+
+    #[test]
+    fn test_registry_identifier_url() {
+        init_logger();
+
+        let urls = [
+            (
+                "MFCD00003568",
+                "https://pubchem.ncbi.nlm.nih.gov/#query=MFCD00003568",
+            ),
+            ("CID 2244", "https://pubchem.ncbi.nlm.nih.gov/compound/2244"),
+            (
+                "SID 135626838",
+                "https://pubchem.ncbi.nlm.nih.gov/substance/135626838",
+            ),
+            (
+                "chebi:15377",
+                "https://www.ebi.ac.uk/chebi/searchId.do?chebiId=CHEBI:15377",
+            ),
+            (
+                "CHEMBL25",
+                "https://www.ebi.ac.uk/chembl/compound_report_card/CHEMBL25/",
+            ),
+            (
+                "DTXSID7020182",
+                "https://comptox.epa.gov/dashboard/chemical/details/DTXSID7020182",
+            ),
+            ("Q283", "https://www.wikidata.org/wiki/Q283"),
+        ];
+        for (identifier, url) in urls {
+            assert_eq!(parse_registry_identifier(identifier).unwrap().url(), url);
+        }
+    }
+}
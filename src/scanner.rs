@@ -0,0 +1,155 @@
+use log::debug;
+use std::ops::Range;
+
+use crate::{
+    casnumber::{DASHES, is_cas_number},
+    cenumber::is_ce_number,
+};
+
+/// Chars that may join digit groups, as in dates, times, phone numbers, decimal numbers or lists.
+/// A run of digits and of these chars is read as a whole.
+const JOINING_CHARS: [char; 12] = ['-', '.', '/', ':', ',', ';', '‐', '‑', '‒', '–', '—', '−'];
+
+/// Chars separating the identifiers of a list, as in `64-17-5, 67-56-1` or `64-17-5/67-56-1`.
+const LIST_SEPARATORS: [char; 3] = [',', '/', ';'];
+
+/// The kind of an identifier found by `scan_identifiers`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentifierKind {
+    CasNumber,
+    EcNumber,
+}
+
+/// An identifier found in a text with its byte span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentifierMatch<'a> {
+    pub kind: IdentifierKind,
+    /// The identifier as written in the text, unicode dashes included.
+    pub text: &'a str,
+    pub span: Range<usize>,
+    /// True if the check digit is valid, see `is_cas_number` and `is_ce_number`.
+    pub is_valid: bool,
+}
+
+/// Finds the CAS and EC numbers in a text, as a SDS converted to text or a supplier email.
+/// Runs of digits and `JOINING_CHARS` are split on `LIST_SEPARATORS` into candidates.
+/// Candidates must have the exact shape of a number, unicode dashes read as `-`:
+/// `NNNNNNN-NN-N` for CAS numbers and `NNN-NNN-N` for EC numbers. Longer candidates, as dates
+/// (`2023-01-15`), phone numbers (`01-23-45-67-89`) or versions (`1.2-34-5`) are skipped,
+/// as candidates preceded by `+` or stuck to a letter.
+/// A candidate looking like a date, as `2023-01-5`, is skipped if its check digit is invalid.
+/// Matches are returned in text order, valid or not.
+#[must_use]
+pub fn scan_identifiers(text: &str) -> Vec<IdentifierMatch<'_>> {
+    let mut matches: Vec<IdentifierMatch> = Vec::new();
+
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, current_char)) = chars.next() {
+        if !current_char.is_ascii_digit() {
+            continue;
+        }
+
+        // The whole run.
+        let mut end = start + 1;
+        while let Some((index, next_char)) =
+            chars.next_if(|(_, c)| c.is_ascii_digit() || JOINING_CHARS.contains(c))
+        {
+            end = index + next_char.len_utf8();
+        }
+
+        // The candidates of the run, the separators are ASCII chars.
+        let mut candidate_start = start;
+        for candidate in text[start..end].split(LIST_SEPARATORS) {
+            if let Some(identifier_match) = scan_candidate(text, candidate_start, candidate) {
+                matches.push(identifier_match);
+            }
+            candidate_start += candidate.len() + 1;
+        }
+    }
+
+    matches
+}
+
+/// Returns the match of a candidate starting at the given byte index of the text, if any.
+fn scan_candidate<'a>(text: &'a str, start: usize, candidate: &str) -> Option<IdentifierMatch<'a>> {
+    // Trailing joining chars, as a final dot, are not part of the candidate.
+    let candidate = candidate.trim_end_matches(|c: char| !c.is_ascii_digit());
+    if candidate.is_empty() {
+        return None;
+    }
+    let end = start + candidate.len();
+
+    let is_stuck_before = text[..start]
+        .chars()
+        .next_back()
+        .is_some_and(|c| c == '+' || c.is_alphanumeric());
+    let is_stuck_after = text[end..].starts_with(char::is_alphanumeric);
+    if is_stuck_before || is_stuck_after {
+        debug!("skipped stuck candidate: {candidate}");
+        return None;
+    }
+
+    let normalized: String = candidate
+        .chars()
+        .map(|c| if DASHES.contains(&c) { '-' } else { c })
+        .collect();
+
+    let kind = identifier_kind(&normalized)?;
+
+    let is_valid = match kind {
+        IdentifierKind::CasNumber => is_cas_number(&normalized).is_ok(),
+        IdentifierKind::EcNumber => is_ce_number(&normalized).is_ok(),
+    };
+
+    if !is_valid && is_date_like(&normalized) {
+        debug!("skipped date: {candidate}");
+        return None;
+    }
+
+    Some(IdentifierMatch {
+        kind,
+        text: &text[start..end],
+        span: start..end,
+        is_valid,
+    })
+}
+
+/// Returns the kind of identifier with the shape of the candidate, if any.
+/// Unicode dashes are read as `-`, as in `normalize_cas_number`.
+fn identifier_kind(candidate: &str) -> Option<IdentifierKind> {
+    let mut groups = candidate.split(|c: char| c == '-' || DASHES.contains(&c));
+    let lengths = [groups.next()?, groups.next()?, groups.next()?].map(|group| {
+        group
+            .bytes()
+            .all(|b| b.is_ascii_digit())
+            .then_some(group.len())
+    });
+
+    if groups.next().is_some() {
+        return None;
+    }
+
+    match lengths {
+        [Some(3), Some(3), Some(1)] => Some(IdentifierKind::EcNumber),
+        [Some(2..=7), Some(2), Some(1)] => Some(IdentifierKind::CasNumber),
+        _ => None,
+    }
+}
+
+/// Returns true if the candidate looks like a date: a year from 1900 to 2099 followed by a month.
+fn is_date_like(candidate: &str) -> bool {
+    let mut groups = candidate.split('-');
+    let (Some(year), Some(month)) = (groups.next(), groups.next()) else {
+        return false;
+    };
+
+    year.len() == 4
+        && (year.starts_with("19") || year.starts_with("20"))
+        && month
+            .parse::<u8>()
+            .is_ok_and(|month| (1..=12).contains(&month))
+}
+
+#[cfg(test)]
+#[path = "scanner_tests.rs"]
+mod scanner_tests;
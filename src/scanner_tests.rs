@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::scanner::*;
+
+    fn init_logger() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    #[test]
+    fn test_scan_identifiers() {
+        init_logger();
+
+        let text =
+            "Ethanol (CAS: 64-17-5, EC 200-578-6).\nFormaldehyde CAS 50-00-1, EC No. 200-001-8.";
+        let matches = scan_identifiers(text);
+
+        assert_eq!(
+            matches
+                .iter()
+                .map(|m| (m.kind, m.text, m.is_valid))
+                .collect::<Vec<_>>(),
+            vec![
+                (IdentifierKind::CasNumber, "64-17-5", true),
+                (IdentifierKind::EcNumber, "200-578-6", true),
+                (IdentifierKind::CasNumber, "50-00-1", false),
+                (IdentifierKind::EcNumber, "200-001-8", true),
+            ]
+        );
+        assert_eq!(&text[matches[0].span.clone()], "64-17-5");
+        assert_eq!(&text[matches[3].span.clone()], "200-001-8");
+
+        // Spans are byte spans.
+        let matches = scan_identifiers("Éthanol – n° CAS : 64-17-5");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].span, 23..30);
+
+        assert_eq!(scan_identifiers("water: 7732-18-5")[0].text, "7732-18-5");
+        assert_eq!(scan_identifiers("7732-18-5")[0].span, 0..9);
+    }
+
+    #[test]
+    fn test_scan_identifiers_lists_and_dashes() {
+        init_logger();
+
+        let kinds_texts = |text| {
+            scan_identifiers(text)
+                .into_iter()
+                .map(|m| (m.kind, m.text, m.span, m.is_valid))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            kinds_texts("CAS: 64-17-5,67-56-1"),
+            vec![
+                (IdentifierKind::CasNumber, "64-17-5", 5..12, true),
+                (IdentifierKind::CasNumber, "67-56-1", 13..20, true),
+            ]
+        );
+        assert_eq!(
+            kinds_texts("64-17-5/67-56-1"),
+            vec![
+                (IdentifierKind::CasNumber, "64-17-5", 0..7, true),
+                (IdentifierKind::CasNumber, "67-56-1", 8..15, true),
+            ]
+        );
+        assert_eq!(
+            kinds_texts("64-17-5; 200-578-6."),
+            vec![
+                (IdentifierKind::CasNumber, "64-17-5", 0..7, true),
+                (IdentifierKind::EcNumber, "200-578-6", 9..18, true),
+            ]
+        );
+
+        // Unicode dashes, as in the output of pdftotext, spans are byte spans.
+        assert_eq!(
+            kinds_texts("64–17–5"),
+            vec![(IdentifierKind::CasNumber, "64–17–5", 0..11, true)]
+        );
+        assert_eq!(
+            kinds_texts("EC 200‑578‑6, CAS 64‑17‑6"),
+            vec![
+                (IdentifierKind::EcNumber, "200‑578‑6", 3..16, true),
+                (IdentifierKind::CasNumber, "64‑17‑6", 22..33, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_identifiers_look_alikes() {
+        init_logger();
+
+        for text in [
+            "",
+            "no number here",
+            "delivered on 2023-01-15",
+            "delivered on 15-01-2023",
+            "delivered on 2023-01-5",
+            "call 01-23-45-67-89",
+            "call +33-12-3",
+            "call +1 555-123-4567",
+            "at 12:30-45-6",
+            "version 1.2-34-5",
+            "lot A64-17-5",
+            "lot 64-17-5B",
+            "10.5-17-5 mg",
+            "12345678-17-5",
+            "delivered on 15/01/2023",
+        ] {
+            assert_eq!(scan_identifiers(text), vec![], "{text:?}");
+        }
+
+        // A valid number looking like a date is kept.
+        assert_eq!(scan_identifiers("1912-24-9")[0].text, "1912-24-9");
+        assert!(scan_identifiers("1912-24-9")[0].is_valid);
+    }
+}
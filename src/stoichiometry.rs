@@ -0,0 +1,394 @@
+use log::debug;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+use crate::formula::{split_formula_charge, to_atom_counts};
+
+/// Arrows accepted between the reactants and the products.
+/// `=` must have spaces around it, so that double bonds as in `CH2=CH2` are kept.
+const REACTION_ARROWS: [&str; 6] = ["<=>", "->", "→", "⟶", "⇌", " = "];
+
+/// Tolerance on the atom balance when checking that a reaction is balanced.
+const BALANCE_TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, PartialEq)]
+pub enum StoichiometryError {
+    EmptyReaction,
+    MissingArrow,
+    EmptySide,
+    InvalidCoefficient(String),
+    NotBalanced(String),
+    ChargeNotBalanced(f64),
+    UnknownReagent(String),
+    UnknownProduct(String),
+    NoReagentQuantity,
+    InvalidQuantity(String),
+}
+
+impl Display for StoichiometryError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            StoichiometryError::EmptyReaction => write!(f, "empty reaction"),
+            StoichiometryError::MissingArrow => write!(f, "missing reaction arrow"),
+            StoichiometryError::EmptySide => write!(f, "no reactant or no product"),
+            StoichiometryError::InvalidCoefficient(s) => write!(f, "invalid coefficient {s}"),
+            StoichiometryError::NotBalanced(s) => write!(f, "reaction not balanced for {s}"),
+            StoichiometryError::ChargeNotBalanced(charge) => {
+                write!(f, "reaction charge not balanced, {charge} left")
+            }
+            StoichiometryError::UnknownReagent(s) => write!(f, "unknown reagent {s}"),
+            StoichiometryError::UnknownProduct(s) => write!(f, "unknown product {s}"),
+            StoichiometryError::NoReagentQuantity => write!(f, "no reagent quantity"),
+            StoichiometryError::InvalidQuantity(s) => write!(f, "invalid quantity for {s}"),
+        }
+    }
+}
+
+impl std::error::Error for StoichiometryError {}
+
+/// A reactant or a product of a reaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReactionComponent {
+    /// The formula as written in the reaction.
+    pub formula: String,
+    pub coefficient: f64,
+    /// The charge, from `split_formula_charge`.
+    pub charge: isize,
+    /// The molar mass in g/mol.
+    pub molar_mass: f64,
+}
+
+/// A balanced reaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reaction {
+    pub reactants: Vec<ReactionComponent>,
+    pub products: Vec<ReactionComponent>,
+}
+
+/// The quantity of a reagent or a product.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quantity {
+    /// A mass in g.
+    Mass(f64),
+    /// A volume in mL with its density in g/mL.
+    Volume { volume: f64, density: f64 },
+    /// An amount in mol.
+    Moles(f64),
+}
+
+impl Quantity {
+    /// Returns the amount in mol for the given molar mass in g/mol.
+    #[must_use]
+    pub fn to_moles(&self, molar_mass: f64) -> f64 {
+        match self {
+            Quantity::Mass(mass) => mass / molar_mass,
+            Quantity::Volume { volume, density } => volume * density / molar_mass,
+            Quantity::Moles(moles) => *moles,
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        let values = match self {
+            Quantity::Mass(value) | Quantity::Moles(value) => [*value, 1.0],
+            Quantity::Volume { volume, density } => [*volume, *density],
+        };
+
+        values
+            .iter()
+            .all(|value| value.is_finite() && *value >= 0.0)
+    }
+}
+
+/// The amounts of a reagent for the reaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReagentAmount {
+    pub formula: String,
+    /// The available amount in mol, `None` if the reagent is in excess without a given quantity.
+    pub available_moles: Option<f64>,
+    /// The amount consumed by the reaction in mol.
+    pub consumed_moles: f64,
+    /// The mass consumed by the reaction in g.
+    pub consumed_mass: f64,
+    /// The remaining amount in mol, `None` if the reagent is in excess without a given quantity.
+    pub excess_moles: Option<f64>,
+}
+
+/// The theoretical amounts of a product.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductAmount {
+    pub formula: String,
+    pub moles: f64,
+    /// The mass in g.
+    pub mass: f64,
+}
+
+/// The result of a stoichiometry computation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoichiometryResult {
+    /// The formula of the limiting reagent as written in the reaction.
+    pub limiting_reagent: String,
+    /// The extent of the reaction in mol.
+    pub extent: f64,
+    pub reagents: Vec<ReagentAmount>,
+    pub products: Vec<ProductAmount>,
+}
+
+impl StoichiometryResult {
+    /// Returns the percent yield of a product from its actual quantity.
+    pub fn percent_yield(
+        &self,
+        product: &str,
+        actual: Quantity,
+    ) -> Result<f64, Box<dyn Error + Send + Sync>> {
+        let Some(product_amount) = self.products.iter().find(|p| p.formula == product) else {
+            return Err(Box::new(StoichiometryError::UnknownProduct(
+                product.to_string(),
+            )));
+        };
+
+        if !actual.is_valid() {
+            return Err(Box::new(StoichiometryError::InvalidQuantity(
+                product.to_string(),
+            )));
+        }
+
+        if product_amount.moles <= 0.0 {
+            return Ok(0.0);
+        }
+
+        let molar_mass = product_amount.mass / product_amount.moles;
+
+        Ok(actual.to_moles(molar_mass) * 100.0 / product_amount.moles)
+    }
+}
+
+/// Parses a balanced reaction as `2 H2 + O2 -> 2 H2O`.
+/// Components are separated by ` + ` with spaces, so that charges as in `Ag+ + Cl- -> AgCl` are kept.
+/// Accepted arrows: `->`, `→`, `⟶`, `<=>`, `⇌` and ` = ` with spaces.
+/// Both the atoms and the net charge must be balanced.
+/// Monatomic ions may carry their charge magnitude, `Fe3+`, and electrons are written `e-`:
+/// `Fe3+ + e- -> Fe2+`. See `split_formula_charge`.
+pub fn parse_reaction(reaction: &str) -> Result<Reaction, Box<dyn Error + Send + Sync>> {
+    let reaction = reaction.trim();
+
+    if reaction.is_empty() {
+        return Err(Box::new(StoichiometryError::EmptyReaction));
+    }
+
+    // Splitting on the first arrow found.
+    let Some((reactants_side, products_side)) = REACTION_ARROWS
+        .iter()
+        .find_map(|arrow| reaction.split_once(arrow))
+    else {
+        return Err(Box::new(StoichiometryError::MissingArrow));
+    };
+
+    let reactants = parse_reaction_side(reactants_side)?;
+    let products = parse_reaction_side(products_side)?;
+
+    if reactants.is_empty() || products.is_empty() {
+        return Err(Box::new(StoichiometryError::EmptySide));
+    }
+
+    // Checking the balance.
+    let mut atom_balance: HashMap<String, f64> = HashMap::new();
+    let mut charge_balance = 0.0;
+
+    for (components, sign) in [(&reactants, 1.0), (&products, -1.0)] {
+        for component in components {
+            #[allow(clippy::cast_precision_loss)]
+            let charge = component.charge as f64;
+            charge_balance += sign * component.coefficient * charge;
+
+            // Counting the atoms of one formula unit, as `CaSO4·0.5H2O` is counted for 2.
+            let (formula, _) = split_formula_charge(&component.formula);
+            let atom_counts = to_atom_counts(formula)?;
+            #[allow(clippy::cast_precision_loss)]
+            let formula_units = atom_counts.formula_units() as f64;
+
+            for (element, atom_count) in atom_counts.iter() {
+                #[allow(clippy::cast_precision_loss)]
+                let atom_count = atom_count as f64 / formula_units;
+                *atom_balance.entry(element.symbol.to_string()).or_default() +=
+                    sign * component.coefficient * atom_count;
+            }
+        }
+    }
+
+    debug!("atom_balance: {atom_balance:#?} charge_balance: {charge_balance}");
+
+    let mut unbalanced_atoms: Vec<&String> = atom_balance
+        .iter()
+        .filter(|(_, balance)| balance.abs() > BALANCE_TOLERANCE)
+        .map(|(atom_name, _)| atom_name)
+        .collect();
+
+    if !unbalanced_atoms.is_empty() {
+        unbalanced_atoms.sort();
+
+        return Err(Box::new(StoichiometryError::NotBalanced(
+            unbalanced_atoms
+                .iter()
+                .map(|atom_name| atom_name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", "),
+        )));
+    }
+
+    if charge_balance.abs() > BALANCE_TOLERANCE {
+        return Err(Box::new(StoichiometryError::ChargeNotBalanced(
+            charge_balance,
+        )));
+    }
+
+    Ok(Reaction {
+        reactants,
+        products,
+    })
+}
+
+/// Parses one side of a reaction.
+fn parse_reaction_side(side: &str) -> Result<Vec<ReactionComponent>, Box<dyn Error + Send + Sync>> {
+    let mut components: Vec<ReactionComponent> = Vec::new();
+
+    for component in side.split(" + ") {
+        let component = component.trim();
+
+        if component.is_empty() {
+            continue;
+        }
+
+        // Formulas never begin with a digit, a leading number is the coefficient.
+        let formula_index = component
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(component.len());
+        let (coefficient_string, formula) = component.split_at(formula_index);
+        let formula = formula.trim();
+
+        let coefficient = if coefficient_string.is_empty() {
+            1.0
+        } else {
+            match coefficient_string.parse::<f64>() {
+                Ok(coefficient) if coefficient > 0.0 => coefficient,
+                _ => {
+                    return Err(Box::new(StoichiometryError::InvalidCoefficient(
+                        component.to_string(),
+                    )));
+                }
+            }
+        };
+
+        let (neutral_formula, charge) = split_formula_charge(formula);
+        let molar_mass = to_atom_counts(neutral_formula)?.molar_mass();
+
+        debug!("component: {coefficient} {formula} {molar_mass}");
+
+        components.push(ReactionComponent {
+            formula: formula.to_string(),
+            coefficient,
+            charge,
+            molar_mass,
+        });
+    }
+
+    Ok(components)
+}
+
+/// Computes the limiting reagent and the theoretical product amounts of a reaction.
+/// Quantities are given for the reactants by their formula as written in the reaction.
+/// Reactants without quantity are considered in excess.
+pub fn compute_stoichiometry(
+    reaction: &Reaction,
+    quantities: &[(&str, Quantity)],
+) -> Result<StoichiometryResult, Box<dyn Error + Send + Sync>> {
+    if quantities.is_empty() {
+        return Err(Box::new(StoichiometryError::NoReagentQuantity));
+    }
+
+    // Available moles by reactant index.
+    let mut available_moles: Vec<Option<f64>> = vec![None; reaction.reactants.len()];
+
+    for (formula, quantity) in quantities {
+        let Some(index) = reaction
+            .reactants
+            .iter()
+            .position(|reactant| reactant.formula == *formula)
+        else {
+            return Err(Box::new(StoichiometryError::UnknownReagent(
+                (*formula).to_string(),
+            )));
+        };
+
+        if !quantity.is_valid() {
+            return Err(Box::new(StoichiometryError::InvalidQuantity(
+                (*formula).to_string(),
+            )));
+        }
+
+        let moles = quantity.to_moles(reaction.reactants[index].molar_mass);
+        *available_moles[index].get_or_insert(0.0) += moles;
+    }
+
+    debug!("available_moles: {available_moles:?}");
+
+    // The limiting reagent gives the smallest extent.
+    let mut limiting: Option<(usize, f64)> = None;
+
+    for (index, reactant) in reaction.reactants.iter().enumerate() {
+        if let Some(moles) = available_moles[index] {
+            let extent = moles / reactant.coefficient;
+
+            if limiting.is_none_or(|(_, limiting_extent)| extent < limiting_extent) {
+                limiting = Some((index, extent));
+            }
+        }
+    }
+
+    // We have at least one quantity.
+    let (limiting_index, extent) = limiting.unwrap();
+
+    let reagents = reaction
+        .reactants
+        .iter()
+        .zip(&available_moles)
+        .map(|(reactant, available)| {
+            let consumed_moles = reactant.coefficient * extent;
+
+            ReagentAmount {
+                formula: reactant.formula.clone(),
+                available_moles: *available,
+                consumed_moles,
+                consumed_mass: consumed_moles * reactant.molar_mass,
+                excess_moles: available.map(|moles| (moles - consumed_moles).max(0.0)),
+            }
+        })
+        .collect();
+
+    let products = reaction
+        .products
+        .iter()
+        .map(|product| {
+            let moles = product.coefficient * extent;
+
+            ProductAmount {
+                formula: product.formula.clone(),
+                moles,
+                mass: moles * product.molar_mass,
+            }
+        })
+        .collect();
+
+    Ok(StoichiometryResult {
+        limiting_reagent: reaction.reactants[limiting_index].formula.clone(),
+        extent,
+        reagents,
+        products,
+    })
+}
+
+#[cfg(test)]
+#[path = "stoichiometry_tests.rs"]
+mod stoichiometry_tests;
@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::{stoichiometry::*, test_helpers::assert_close};
+
+    fn init_logger() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    /// Tolerance of `assert_close`.
+    const TOLERANCE: f64 = 0.01;
+
+    #[test]
+    fn test_parse_reaction() {
+        init_logger();
+
+        let reaction = parse_reaction("2 H2 + O2 -> 2 H2O").unwrap();
+        assert_eq!(reaction.reactants.len(), 2);
+        assert_eq!(reaction.reactants[0].formula, "H2");
+        assert_close(2.0, reaction.reactants[0].coefficient, TOLERANCE);
+        assert_eq!(reaction.reactants[1].formula, "O2");
+        assert_close(1.0, reaction.reactants[1].coefficient, TOLERANCE);
+        assert_eq!(reaction.products[0].formula, "H2O");
+        assert_close(18.015, reaction.products[0].molar_mass, TOLERANCE);
+
+        assert!(parse_reaction("2H2 + O2 → 2H2O").is_ok());
+        assert!(parse_reaction("CH3COOH + C2H5OH <=> CH3COOC2H5 + H2O").is_ok());
+        assert!(parse_reaction("Ag+ + Cl- -> AgCl").is_ok());
+        assert!(parse_reaction("H2 + 0.5 O2 = H2O").is_ok());
+
+        // Double bonds are not arrows.
+        let reaction = parse_reaction("CH2=CH2 + H2 = C2H6").unwrap();
+        assert_eq!(reaction.reactants[0].formula, "CH2=CH2");
+        assert_eq!(reaction.products[0].formula, "C2H6");
+        assert!(parse_reaction("CH2=CH2 + H2 -> C2H6").is_ok());
+        assert_eq!(
+            parse_reaction("H2 + 0.5 O2=H2O").unwrap_err().to_string(),
+            "missing reaction arrow"
+        );
+
+        // Decimal hydrates are counted for one formula unit.
+        let reaction = parse_reaction("2 CaSO4·0.5H2O -> 2 CaSO4 + H2O").unwrap();
+        assert_close(145.142, reaction.reactants[0].molar_mass, TOLERANCE);
+        assert!(parse_reaction("CaSO4·0.5H2O + 1.5 H2O -> CaSO4·2H2O").is_ok());
+
+        // Ionic equations, the charge must be balanced.
+        let reaction = parse_reaction("2 Fe^3+ + Zn -> 2 Fe^2+ + Zn^2+").unwrap();
+        assert_eq!(reaction.reactants[0].charge, 3);
+        assert_close(55.845, reaction.reactants[0].molar_mass, TOLERANCE);
+        assert_eq!(
+            parse_reaction("Fe^3+ + Zn -> Fe^2+ + Zn^2+")
+                .unwrap_err()
+                .to_string(),
+            "reaction charge not balanced, -1 left"
+        );
+        let reaction = parse_reaction("Fe3+ + e- -> Fe2+").unwrap();
+        assert_eq!(reaction.reactants[0].charge, 3);
+        assert_eq!(reaction.reactants[1].charge, -1);
+        assert_eq!(reaction.products[0].charge, 2);
+        let reaction = parse_reaction("Cu2+ + 2 OH- -> Cu(OH)2").unwrap();
+        assert_eq!(reaction.reactants[0].formula, "Cu2+");
+        assert_eq!(reaction.reactants[0].charge, 2);
+        assert_close(63.546, reaction.reactants[0].molar_mass, TOLERANCE);
+        assert_eq!(
+            parse_reaction("Cu2+ + OH- -> Cu(OH)2")
+                .unwrap_err()
+                .to_string(),
+            "reaction not balanced for H, O"
+        );
+        assert_eq!(
+            parse_reaction("Ag+ + Cl -> AgCl").unwrap_err().to_string(),
+            "reaction charge not balanced, 1 left"
+        );
+
+        assert_eq!(
+            parse_reaction("H2 + O2 -> H2O").unwrap_err().to_string(),
+            "reaction not balanced for O"
+        );
+        assert_eq!(
+            parse_reaction("").unwrap_err().to_string(),
+            "empty reaction"
+        );
+        assert_eq!(
+            parse_reaction("H2 + O2").unwrap_err().to_string(),
+            "missing reaction arrow"
+        );
+        assert_eq!(
+            parse_reaction("H2 -> ").unwrap_err().to_string(),
+            "no reactant or no product"
+        );
+        assert_eq!(
+            parse_reaction("0 H2 -> 0 H2").unwrap_err().to_string(),
+            "invalid coefficient 0 H2"
+        );
+        assert!(parse_reaction("Xx -> Xx").is_err());
+    }
+
+    #[test]
+    fn test_compute_stoichiometry() {
+        init_logger();
+
+        let reaction = parse_reaction("2 H2 + O2 -> 2 H2O").unwrap();
+        let result = compute_stoichiometry(
+            &reaction,
+            &[("H2", Quantity::Mass(4.0)), ("O2", Quantity::Mass(32.0))],
+        )
+        .unwrap();
+        assert_eq!(result.limiting_reagent, "H2");
+        assert_close(0.992, result.extent, TOLERANCE);
+        assert_close(1.984, result.products[0].moles, TOLERANCE);
+        assert_close(35.744, result.products[0].mass, TOLERANCE);
+        assert_close(0.0, result.reagents[0].excess_moles.unwrap(), TOLERANCE);
+        assert_close(0.008, result.reagents[1].excess_moles.unwrap(), TOLERANCE);
+        assert_close(0.992, result.reagents[1].consumed_moles, TOLERANCE);
+
+        // Volume, moles and a reagent in excess.
+        let reaction = parse_reaction("CH3COOH + C2H5OH -> CH3COOC2H5 + H2O").unwrap();
+        let result = compute_stoichiometry(
+            &reaction,
+            &[
+                (
+                    "CH3COOH",
+                    Quantity::Volume {
+                        volume: 6.0,
+                        density: 1.049,
+                    },
+                ),
+                ("C2H5OH", Quantity::Moles(0.2)),
+            ],
+        )
+        .unwrap();
+        assert_eq!(result.limiting_reagent, "CH3COOH");
+        assert_close(0.1048, result.products[0].moles, TOLERANCE);
+        assert_close(9.234, result.products[0].mass, TOLERANCE);
+        assert_close(
+            64.98,
+            result
+                .percent_yield("CH3COOC2H5", Quantity::Mass(6.0))
+                .unwrap(),
+            TOLERANCE,
+        );
+        assert!(
+            result
+                .percent_yield("CH3COOH", Quantity::Mass(6.0))
+                .is_err()
+        );
+
+        let result = compute_stoichiometry(&reaction, &[("C2H5OH", Quantity::Moles(0.2))]).unwrap();
+        assert_eq!(result.limiting_reagent, "C2H5OH");
+        assert_eq!(result.reagents[0].available_moles, None);
+        assert_eq!(result.reagents[0].excess_moles, None);
+        assert_close(0.2, result.reagents[0].consumed_moles, TOLERANCE);
+
+        assert_eq!(
+            compute_stoichiometry(&reaction, &[])
+                .unwrap_err()
+                .to_string(),
+            "no reagent quantity"
+        );
+        assert_eq!(
+            compute_stoichiometry(&reaction, &[("NaCl", Quantity::Moles(1.0))])
+                .unwrap_err()
+                .to_string(),
+            "unknown reagent NaCl"
+        );
+        assert_eq!(
+            compute_stoichiometry(&reaction, &[("C2H5OH", Quantity::Mass(-1.0))])
+                .unwrap_err()
+                .to_string(),
+            "invalid quantity for C2H5OH"
+        );
+    }
+}
@@ -25,6 +25,64 @@ pub fn clean(s: &str, transform: Transform) -> String {
     }
 }
 
+/// Roman numerals and their values, in decreasing order.
+const ROMAN_NUMERALS: [(&str, u16); 13] = [
+    ("M", 1000),
+    ("CM", 900),
+    ("D", 500),
+    ("CD", 400),
+    ("C", 100),
+    ("XC", 90),
+    ("L", 50),
+    ("XL", 40),
+    ("X", 10),
+    ("IX", 9),
+    ("V", 5),
+    ("IV", 4),
+    ("I", 1),
+];
+
+/// Converts a number into uppercase roman numerals.
+/// Returns an empty string for 0.
+#[must_use]
+pub fn to_roman(number: u16) -> String {
+    let mut result = String::new();
+    let mut remainder = number;
+
+    for (numeral, value) in ROMAN_NUMERALS {
+        while remainder >= value {
+            result.push_str(numeral);
+            remainder -= value;
+        }
+    }
+
+    result
+}
+
+/// Converts roman numerals into a number.
+/// The numerals are case insensitive and must be in canonical form (IV and not IIII).
+#[must_use]
+pub fn from_roman(numerals: &str) -> Option<u16> {
+    let uppercase_numerals = numerals.trim().to_uppercase();
+
+    let mut remainder = uppercase_numerals.as_str();
+    let mut number: u16 = 0;
+
+    for (numeral, value) in ROMAN_NUMERALS {
+        while let Some(rest) = remainder.strip_prefix(numeral) {
+            number = number.checked_add(value)?;
+            remainder = rest;
+        }
+    }
+
+    // Rejecting unknown chars and non canonical forms.
+    if !remainder.is_empty() || number == 0 || to_roman(number) != uppercase_numerals {
+        return None;
+    }
+
+    Some(number)
+}
+
 #[cfg(test)]
 #[path = "string_tests.rs"]
 mod string_tests;
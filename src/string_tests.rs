@@ -7,7 +7,7 @@ mod tests {
         clippy::too_many_lines
     )]
 
-    use crate::string::{Transform, clean};
+    use crate::string::{Transform, clean, from_roman, to_roman};
 
     #[test]
     fn test_clean() {
@@ -78,4 +78,24 @@ mod tests {
             clean(" a  ,   b  .   c  ! ", Transform::None)
         );
     }
+
+    #[test]
+    fn test_roman() {
+        assert_eq!("", to_roman(0));
+        assert_eq!("I", to_roman(1));
+        assert_eq!("IV", to_roman(4));
+        assert_eq!("VIII", to_roman(8));
+        assert_eq!("XIV", to_roman(14));
+        assert_eq!("MCMXCIV", to_roman(1994));
+
+        assert_eq!(Some(3), from_roman("III"));
+        assert_eq!(Some(3), from_roman("iii"));
+        assert_eq!(Some(7), from_roman(" VII "));
+        assert_eq!(Some(1994), from_roman("MCMXCIV"));
+        assert_eq!(None, from_roman(""));
+        assert_eq!(None, from_roman("IIII"));
+        assert_eq!(None, from_roman("IIV"));
+        assert_eq!(None, from_roman("3"));
+        assert_eq!(None, from_roman(&"M".repeat(70)));
+    }
 }
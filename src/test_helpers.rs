@@ -0,0 +1,8 @@
+/// Asserts that a value is within `tolerance` of the expected value.
+#[track_caller]
+pub(crate) fn assert_close(expected: f64, value: f64, tolerance: f64) {
+    assert!(
+        (expected - value).abs() < tolerance,
+        "expected {expected}, got {value}"
+    );
+}
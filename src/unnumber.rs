@@ -0,0 +1,287 @@
+use log::debug;
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// Lowest UN number assigned, UN0004 ammonium picrate.
+const MIN_UN_NUMBER: u16 = 4;
+
+/// Highest UN number assigned, in the 2025 edition of the UN Model Regulations.
+/// Later editions add numbers, see `normalize_un_number_with_max`.
+pub const DEFAULT_MAX_UN_NUMBER: u16 = 3558;
+
+/// Highest four digits number, accepted by `UnNumberTable` whose records tell the assigned numbers.
+const MAX_FOUR_DIGITS_NUMBER: u16 = 9999;
+
+/// ADR classes and divisions.
+const ADR_CLASSES: [&str; 19] = [
+    "1", "1.1", "1.2", "1.3", "1.4", "1.5", "1.6", "2", "3", "4.1", "4.2", "4.3", "5.1", "5.2",
+    "6.1", "6.2", "7", "8", "9",
+];
+
+#[derive(Debug, PartialEq)]
+pub enum UnNumberError {
+    EmptyUnNumber,
+    InvalidFormat(String),
+    OutOfRange(u16, u16),
+    InvalidAdrClass(String),
+    InvalidPackingGroup(String),
+    InvalidRecord(usize),
+}
+
+impl Display for UnNumberError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match &self {
+            UnNumberError::EmptyUnNumber => write!(f, "empty UN number"),
+            UnNumberError::InvalidFormat(s) => write!(f, "invalid UN number format {s}"),
+            UnNumberError::OutOfRange(number, max_un_number) => write!(
+                f,
+                "UN{number:04} is out of range UN{MIN_UN_NUMBER:04}-UN{max_un_number:04}"
+            ),
+            UnNumberError::InvalidAdrClass(s) => write!(f, "invalid ADR class {s}"),
+            UnNumberError::InvalidPackingGroup(s) => write!(f, "invalid packing group {s}"),
+            UnNumberError::InvalidRecord(line) => write!(f, "invalid record at line {line}"),
+        }
+    }
+}
+
+impl std::error::Error for UnNumberError {}
+
+/// A UN number that went through `normalize_un_number`, as `UN1090`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnNumber(u16);
+
+impl UnNumber {
+    /// Returns the number, 1090 for `UN1090`.
+    #[must_use]
+    pub fn number(&self) -> u16 {
+        self.0
+    }
+}
+
+impl FromStr for UnNumber {
+    type Err = Box<dyn std::error::Error + Send + Sync>;
+
+    fn from_str(number: &str) -> Result<Self, Self::Err> {
+        normalize_un_number(number)
+    }
+}
+
+impl TryFrom<&str> for UnNumber {
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn try_from(number: &str) -> Result<Self, Self::Error> {
+        number.parse()
+    }
+}
+
+impl Display for UnNumber {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "UN{:04}", self.0)
+    }
+}
+
+/// Normalizes a UN number: `UN1090`, `1090`, `UN 1090`, `un-1090`, `UN No 1090` and `UN No. 1090`
+/// give `UN1090`.
+/// The number must have four digits and be assigned, from `UN0004` to `DEFAULT_MAX_UN_NUMBER`.
+pub fn normalize_un_number(
+    number: &str,
+) -> Result<UnNumber, Box<dyn std::error::Error + Send + Sync>> {
+    normalize_un_number_with_max(number, DEFAULT_MAX_UN_NUMBER)
+}
+
+/// Normalizes a UN number, as `normalize_un_number`, with the highest assigned number
+/// of the edition of the UN Model Regulations in use.
+pub fn normalize_un_number_with_max(
+    number: &str,
+    max_un_number: u16,
+) -> Result<UnNumber, Box<dyn std::error::Error + Send + Sync>> {
+    let trimmed_number = number.trim();
+
+    if trimmed_number.is_empty() {
+        return Err(Box::new(UnNumberError::EmptyUnNumber));
+    }
+
+    // Removing the prefix and its separator.
+    let digits = match trimmed_number.get(..2) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("UN") => {
+            let rest = trimmed_number[2..].trim_start();
+            let rest = rest
+                .get(..2)
+                .filter(|no| no.eq_ignore_ascii_case("No"))
+                .map_or(rest, |_| rest[2..].strip_prefix('.').unwrap_or(&rest[2..]));
+
+            rest.trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '-' | ':'))
+        }
+        _ => trimmed_number,
+    };
+
+    if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Box::new(UnNumberError::InvalidFormat(number.to_string())));
+    }
+
+    let value: u16 = digits.parse()?;
+    debug!("value:{value}");
+
+    if !(MIN_UN_NUMBER..=max_un_number).contains(&value) {
+        return Err(Box::new(UnNumberError::OutOfRange(value, max_un_number)));
+    }
+
+    Ok(UnNumber(value))
+}
+
+/// Check if a string is a valid UN number, see `normalize_un_number`.
+pub fn is_un_number(number: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    normalize_un_number(number).map(|_| ())
+}
+
+/// The packing group, the degree of danger of a substance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PackingGroup {
+    /// High danger.
+    I,
+    /// Medium danger.
+    II,
+    /// Low danger.
+    III,
+}
+
+impl FromStr for PackingGroup {
+    type Err = Box<dyn std::error::Error + Send + Sync>;
+
+    fn from_str(packing_group: &str) -> Result<Self, Self::Err> {
+        match packing_group.trim() {
+            "I" | "1" => Ok(PackingGroup::I),
+            "II" | "2" => Ok(PackingGroup::II),
+            "III" | "3" => Ok(PackingGroup::III),
+            _ => Err(Box::new(UnNumberError::InvalidPackingGroup(
+                packing_group.to_string(),
+            ))),
+        }
+    }
+}
+
+impl Display for PackingGroup {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            PackingGroup::I => write!(f, "I"),
+            PackingGroup::II => write!(f, "II"),
+            PackingGroup::III => write!(f, "III"),
+        }
+    }
+}
+
+/// The transport data of a UN number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransportInfo {
+    pub proper_shipping_name: String,
+    /// The ADR class or division, as `3` or `6.1`.
+    pub adr_class: String,
+    /// `None` for the classes without packing group, as gases.
+    pub packing_group: Option<PackingGroup>,
+}
+
+/// A local lookup table of UN numbers, filled by the application from its own data.
+/// A UN number may have several entries, as `UN1993` for several packing groups.
+/// The records tell which numbers are assigned: any four digits number from `UN0004` is accepted,
+/// so that the table follows the edition of the UN Model Regulations of the data.
+#[derive(Debug, Clone, Default)]
+pub struct UnNumberTable {
+    entries: HashMap<UnNumber, Vec<TransportInfo>>,
+}
+
+impl UnNumberTable {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an entry, the ADR class is validated.
+    pub fn insert(
+        &mut self,
+        un_number: UnNumber,
+        transport_info: TransportInfo,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if !ADR_CLASSES.contains(&transport_info.adr_class.as_str()) {
+            return Err(Box::new(UnNumberError::InvalidAdrClass(
+                transport_info.adr_class,
+            )));
+        }
+
+        self.entries
+            .entry(un_number)
+            .or_default()
+            .push(transport_info);
+
+        Ok(())
+    }
+
+    /// Builds a table from records, one per line: `UN1090;ACETONE;3;II`.
+    /// The packing group may be empty. Empty lines and lines beginning with `#` are skipped.
+    pub fn from_records(records: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut table = UnNumberTable::new();
+
+        for (index, line) in records.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split(';').map(str::trim).collect();
+            let [un_number, proper_shipping_name, adr_class, packing_group] = fields.as_slice()
+            else {
+                return Err(Box::new(UnNumberError::InvalidRecord(index + 1)));
+            };
+
+            let packing_group = match *packing_group {
+                "" => None,
+                packing_group => Some(packing_group.parse()?),
+            };
+
+            table.insert(
+                normalize_un_number_with_max(un_number, MAX_FOUR_DIGITS_NUMBER)?,
+                TransportInfo {
+                    proper_shipping_name: (*proper_shipping_name).to_string(),
+                    adr_class: (*adr_class).to_string(),
+                    packing_group,
+                },
+            )?;
+        }
+
+        Ok(table)
+    }
+
+    /// Returns the entries of a UN number, empty if unknown.
+    #[must_use]
+    pub fn get(&self, un_number: UnNumber) -> &[TransportInfo] {
+        self.entries.get(&un_number).map_or(&[], Vec::as_slice)
+    }
+
+    /// Normalizes a UN number and returns its entries, empty if unknown.
+    pub fn lookup(
+        &self,
+        number: &str,
+    ) -> Result<&[TransportInfo], Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.get(normalize_un_number_with_max(
+            number,
+            MAX_FOUR_DIGITS_NUMBER,
+        )?))
+    }
+
+    /// Returns the number of distinct UN numbers, not the number of entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+#[path = "unnumber_tests.rs"]
+mod unnumber_tests;
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    #![allow(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::too_many_lines
+    )]
+
+    use crate::unnumber::*;
+
+    fn init_logger() {
+        let _ = env_logger::builder().is_test(true).try_init();
+    }
+
+    #[test]
+    fn test_normalize_un_number() {
+        init_logger();
+
+        for number in [
+            "UN1090",
+            "1090",
+            "UN 1090",
+            "un1090",
+            "UN-1090",
+            "UN No. 1090",
+            "UN No 1090",
+            "un no1090",
+            " UN1090 ",
+        ] {
+            let un_number = normalize_un_number(number).unwrap();
+            assert_eq!(un_number.number(), 1090, "{number:?}");
+            assert_eq!(un_number.to_string(), "UN1090");
+        }
+
+        assert_eq!(normalize_un_number("0004").unwrap().to_string(), "UN0004");
+        assert!(is_un_number("UN3558").is_ok());
+
+        // Numbers added by a later edition.
+        assert_eq!(
+            normalize_un_number_with_max("UN3600", 3600)
+                .unwrap()
+                .to_string(),
+            "UN3600"
+        );
+        assert_eq!(
+            normalize_un_number_with_max("UN3601", 3600)
+                .unwrap_err()
+                .to_string(),
+            "UN3601 is out of range UN0004-UN3600"
+        );
+        assert_eq!(
+            "UN1090".parse::<UnNumber>().unwrap(),
+            UnNumber::try_from("1090").unwrap()
+        );
+
+        assert_eq!(
+            normalize_un_number("").unwrap_err().to_string(),
+            "empty UN number"
+        );
+        assert_eq!(
+            normalize_un_number("UN0001").unwrap_err().to_string(),
+            "UN0001 is out of range UN0004-UN3558"
+        );
+        assert_eq!(
+            normalize_un_number("UN9999").unwrap_err().to_string(),
+            "UN9999 is out of range UN0004-UN3558"
+        );
+        assert_eq!(
+            normalize_un_number("UN109").unwrap_err().to_string(),
+            "invalid UN number format UN109"
+        );
+        for number in ["UN10900", "UN1O90", "NA1993", "UN 10 90", "1090a", "UN"] {
+            assert!(is_un_number(number).is_err(), "{number:?}");
+        }
+    }
+
+    #[test]
+    fn test_un_number_table() {
+        init_logger();
+
+        let table = UnNumberTable::from_records(
+            "# un_number;proper_shipping_name;adr_class;packing_group
+UN1090;ACETONE;3;II
+UN1005;AMMONIA, ANHYDROUS;2;
+
+UN1993;FLAMMABLE LIQUID, N.O.S.;3;I
+UN1993;FLAMMABLE LIQUID, N.O.S.;3;III",
+        )
+        .unwrap();
+        // Distinct UN numbers, UN1993 has two entries.
+        assert_eq!(table.len(), 3);
+
+        let entries = table.lookup("UN 1090").unwrap();
+        assert_eq!(
+            entries,
+            [TransportInfo {
+                proper_shipping_name: "ACETONE".to_string(),
+                adr_class: "3".to_string(),
+                packing_group: Some(PackingGroup::II),
+            }]
+        );
+        assert_eq!(entries[0].packing_group.unwrap().to_string(), "II");
+
+        assert_eq!(table.lookup("1005").unwrap()[0].packing_group, None);
+        assert_eq!(table.lookup("1993").unwrap().len(), 2);
+        assert!(table.lookup("1230").unwrap().is_empty());
+        assert!(table.lookup("12").is_err());
+
+        // The records tell the assigned numbers.
+        let table = UnNumberTable::from_records("UN3600;NEW SUBSTANCE;9;III").unwrap();
+        assert_eq!(table.lookup("UN3600").unwrap().len(), 1);
+        assert!(table.lookup("UN3601").unwrap().is_empty());
+
+        let mut table = UnNumberTable::new();
+        assert!(table.is_empty());
+        assert_eq!(
+            table
+                .insert(
+                    "1090".parse().unwrap(),
+                    TransportInfo {
+                        proper_shipping_name: "ACETONE".to_string(),
+                        adr_class: "10".to_string(),
+                        packing_group: None,
+                    },
+                )
+                .unwrap_err()
+                .to_string(),
+            "invalid ADR class 10"
+        );
+
+        assert_eq!(
+            UnNumberTable::from_records("UN1090;ACETONE;3")
+                .unwrap_err()
+                .to_string(),
+            "invalid record at line 1"
+        );
+        assert_eq!(
+            UnNumberTable::from_records("UN1090;ACETONE;3;IV")
+                .unwrap_err()
+                .to_string(),
+            "invalid packing group IV"
+        );
+    }
+}